@@ -682,6 +682,34 @@ export class ExecutionClientService {
           console.log(`Staker: ${staker.toString()}`);
         }
 
+        // Characters with their own compute mint pay the client in that mint
+        const executionClientCharacterTokenAccount = aiCharacterAccount.characterComputeMint.equals(PublicKey.default)
+          ? null
+          : await anchor.utils.token.associatedAddress({
+              mint: aiCharacterAccount.characterComputeMint,
+              owner: this.wallet.publicKey
+            });
+
+        // The owner markup paid with the message goes to the owner revenue account
+        const messageAccount = await this.program.account.messageAiCharacter.fetch(
+          new PublicKey(message.publicKey)
+        );
+        const ownerRevenueTokenAccount = messageAccount.ownerMarkup.gtn(0)
+          ? PublicKey.findProgramAddressSync(
+              [Buffer.from("owner_revenue"), aiCharacter.toBuffer()],
+              this.program.programId
+            )[0]
+          : null;
+
+        // The protocol fee goes to the protocol treasury of the collection
+        const appAinftAccount = await this.program.account.aiNft.fetch(appAinftPda);
+        const protocolTreasuryTokenAccount = appAinftAccount.protocolFeeBps > 0
+          ? PublicKey.findProgramAddressSync(
+              [Buffer.from("protocol_treasury"), appAinftPda.toBuffer()],
+              this.program.programId
+            )[0]
+          : null;
+
         // Prepare the response object
         const writeResponse = {
           content: response,
@@ -701,6 +729,9 @@ export class ExecutionClientService {
               executionClient: executionClient,
               computeMint: computeMint,
               executionClientComputeTokenAddress: executionClientComputeAccount,
+              executionClientCharacterTokenAccount,
+              ownerRevenueTokenAccount,
+              protocolTreasuryTokenAccount,
               authority: this.wallet.publicKey,
               tokenAAccount: null,
               tokenBAccount: null,
@@ -82,6 +82,78 @@ pub enum AiNftError {
     InvalidActionParams,
     #[msg("Compute mint not initialized")]
     ComputeMintNotInitialized,
+    #[msg("Config version not found in history")]
+    ConfigVersionNotFound,
+    #[msg("Character config is frozen")]
+    ConfigFrozen,
+    #[msg("Missing compute token account")]
+    MissingComputeTokenAccount,
+    #[msg("Invalid model settings")]
+    InvalidModelSettings,
+    #[msg("Invalid collection")]
+    InvalidCollection,
+    #[msg("Invalid royalty configuration")]
+    InvalidRoyalties,
+    #[msg("Invalid treasury recipients")]
+    InvalidTreasuryRecipients,
+    #[msg("Insufficient mint proceeds")]
+    InsufficientProceeds,
+    #[msg("Invalid payment mint")]
+    InvalidPaymentMint,
+    #[msg("Missing payment token accounts")]
+    MissingPaymentAccounts,
+    #[msg("Invalid mint phases")]
+    InvalidMintPhases,
+    #[msg("No mint phase is active")]
+    MintPhaseNotActive,
+    #[msg("Wallet is not on the allowlist")]
+    NotAllowlisted,
+    #[msg("Wallet mint limit reached")]
+    WalletMintLimitReached,
+    #[msg("Ai character has unanswered messages")]
+    PendingMessages,
+    #[msg("Forking is not allowed for this ai character")]
+    ForkNotAllowed,
+    #[msg("Invalid character name")]
+    InvalidCharacterName,
+    #[msg("Invalid metadata uri")]
+    InvalidMetadataUri,
+    #[msg("Ai character is already claimed by this owner")]
+    CharacterAlreadyClaimed,
+    #[msg("Invalid lease")]
+    InvalidLease,
+    #[msg("Lease is still active")]
+    LeaseActive,
+    #[msg("Invalid listing")]
+    InvalidListing,
+    #[msg("Invalid royalty recipients")]
+    InvalidRoyaltyRecipients,
+    #[msg("Owner revenue account is required for messages with an owner markup")]
+    MissingOwnerRevenueAccount,
+    #[msg("Protocol fee exceeds the maximum")]
+    InvalidProtocolFee,
+    #[msg("Protocol treasury account is required when a protocol fee is set")]
+    MissingProtocolTreasuryAccount,
+    #[msg("Invalid protocol limits")]
+    InvalidProtocolLimits,
+    #[msg("Message content is too long")]
+    ContentTooLong,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("This part of the protocol is paused")]
+    ProtocolPaused,
+    #[msg("Character compute mint was already created")]
+    ComputeMintAlreadyCreated,
+    #[msg("Owner markup is not supported for characters with their own compute mint")]
+    OwnerMarkupNotSupported,
+    #[msg("Invalid accepted compute mints")]
+    InvalidAcceptedComputeMints,
+    #[msg("Execution client does not accept the compute mint of the character")]
+    UnsupportedComputeMint,
+    #[msg("Expired lease must be closed first")]
+    LeaseNotClosed,
+    #[msg("Wallet mint count account is required for phases with a per wallet limit")]
+    MissingWalletMintCount,
     InvalidTokenOwner,
     Overflow,
 }
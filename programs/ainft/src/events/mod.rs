@@ -4,6 +4,7 @@ use anchor_lang::prelude::*;
 pub struct CollectionCreated {
     pub collection: Pubkey,
     pub authority: Pubkey,
+    pub collection_id: u64,
 }
 
 #[event]
@@ -55,9 +56,11 @@ pub struct ExecutionClientConfigUpdated {
 
 #[event]
 pub struct CharacterConfigUpdated {
-    pub ai_nft: Pubkey,
+    pub ai_character: Pubkey,
     pub owner: Pubkey,
-    pub new_config: String,
+    pub version: u64,
+    pub previous_config_hash: [u8; 32],
+    pub updated_fields: u32, // see `config_fields`
 }
 
 #[event]
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AiNftError, state::AiNft};
+
+#[event]
+pub struct AuthorityTransferred {
+    pub ai_nft: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.pending_authority == new_authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    pub new_authority: Signer<'info>,
+}
+
+pub fn accept_authority_transfer_handler(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    let previous_authority = ai_nft.authority;
+    ai_nft.accept_authority(ctx.accounts.new_authority.key())?;
+
+    emit!(AuthorityTransferred {
+        ai_nft: ai_nft.key(),
+        previous_authority,
+        new_authority: ai_nft.authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{self, BurnNft, Metadata};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, AiNft, CharacterName, Lease},
+};
+
+use super::claim_owner_revenue::sweep_owner_revenue;
+
+#[event]
+pub struct AiNftBurned {
+    pub ai_character: Pubkey,
+    pub collection: Pubkey,
+    pub owner: Pubkey,
+    pub compute_swept: u64,
+    pub revenue_swept: u64,
+}
+
+#[derive(Accounts)]
+pub struct BurnAiNft<'info> {
+    #[account(
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    // the ai character to burn, its rent goes back to the owner
+    #[account(
+        mut,
+        close = owner,
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    #[account(mut)]
+    pub ai_character_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: registry entry of the character name, closed by the burn. Checked in the
+    /// handler, characters minted before the registry existed have none.
+    #[account(mut)]
+    pub character_name: UncheckedAccount<'info>,
+
+    // token account of the owner holding the agent nft
+    #[account(
+        mut,
+        constraint = owner_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = owner_ai_character_token_account.owner == owner.key() @ AiNftError::InvalidOwner,
+        constraint = owner_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub owner_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metadata of the ai character, closed via CPI to the metadata program
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            ai_character_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub ai_character_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition of the ai character, closed via CPI to the metadata program
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            ai_character_mint.key().as_ref(),
+            "edition".as_bytes(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub ai_character_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Collection metadata, checked against the ai nft account. The collection size is decremented via CPI to the metadata program.
+    #[account(
+        mut,
+        address = ai_nft.master_metadata @ AiNftError::InvalidCollection,
+    )]
+    pub master_metadata: UncheckedAccount<'info>,
+
+    // compute token account of the ai character, required if it was created
+    #[account(
+        mut,
+        constraint = ai_character_compute_token_account.key() == ai_character.load().unwrap().compute_token_account @ AiNftError::InvalidComputeVault,
+    )]
+    pub ai_character_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // receives the remaining compute tokens of the ai character
+    #[account(
+        mut,
+        constraint = owner_compute_token_account.owner == owner.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub owner_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist. A rented character can't be burned.
+    #[account(
+        seeds = ["lease".as_bytes(), ai_character.key().as_ref()],
+        bump,
+        constraint = Lease::load_active(&lease).is_none() @ AiNftError::LeaseActive,
+    )]
+    pub lease: UncheckedAccount<'info>,
+
+    /// CHECK: owner revenue account of the ai character, it may not exist
+    #[account(
+        mut,
+        seeds = ["owner_revenue".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub owner_revenue_token_account: UncheckedAccount<'info>,
+
+    // receives the accrued owner revenue, required if owner revenue accrued
+    #[account(
+        mut,
+        constraint = revenue_recipient_token_account.owner == owner.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub revenue_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> BurnAiNft<'info> {
+    pub fn burn_nft_ctx(&self) -> CpiContext<'_, '_, '_, 'info, BurnNft<'info>> {
+        let program = self.metadata_program.to_account_info();
+        let accounts = BurnNft {
+            metadata: self.ai_character_metadata.to_account_info(),
+            owner: self.owner.to_account_info(),
+            mint: self.ai_character_mint.to_account_info(),
+            token: self.owner_ai_character_token_account.to_account_info(),
+            edition: self.ai_character_master_edition.to_account_info(),
+            spl_token: self.token_program.to_account_info(),
+        };
+        // the collection metadata is not part of the burn accounts
+        CpiContext::new(program, accounts)
+            .with_remaining_accounts(vec![self.master_metadata.to_account_info()])
+    }
+
+    /// Moves the remaining compute tokens to the owner and closes the compute
+    /// token account. Returns the amount of swept compute tokens.
+    #[inline(never)]
+    fn sweep_compute(&self, compute_token_account: Pubkey, seeds: &[&[u8]]) -> Result<u64> {
+        if compute_token_account == Pubkey::default() {
+            return Ok(0);
+        }
+        let ai_character_compute_token_account = self
+            .ai_character_compute_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingComputeTokenAccount)?;
+
+        let amount = ai_character_compute_token_account.amount;
+        if amount > 0 {
+            let owner_compute_token_account = self
+                .owner_compute_token_account
+                .as_ref()
+                .ok_or(AiNftError::MissingComputeTokenAccount)?;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: ai_character_compute_token_account.to_account_info(),
+                        to: owner_compute_token_account.to_account_info(),
+                        authority: self.ai_character.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: ai_character_compute_token_account.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.ai_character.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        Ok(amount)
+    }
+
+    /// Pays out the accrued owner revenue to the owner and closes the owner
+    /// revenue account. Returns the amount of paid out revenue.
+    #[inline(never)]
+    fn sweep_owner_revenue(&self, seeds: &[&[u8]]) -> Result<u64> {
+        let amount = sweep_owner_revenue(
+            &self.token_program,
+            &self.ai_character,
+            &self.owner_revenue_token_account,
+            self.revenue_recipient_token_account.as_deref(),
+        )?;
+        // the revenue account is only created once a markup is set
+        if self.owner_revenue_token_account.owner != &token::ID
+            || self.owner_revenue_token_account.data_is_empty()
+        {
+            return Ok(amount);
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.owner_revenue_token_account.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.ai_character.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        Ok(amount)
+    }
+}
+
+pub fn burn_ainft_handler(ctx: Context<BurnAiNft>) -> Result<()> {
+    let (character_nft_mint, compute_token_account, ai_character_bump, name) = {
+        let ai_character = ctx.accounts.ai_character.load()?;
+        // Don't leave messages behind that were already paid for
+        require!(
+            !ai_character.has_pending_messages(),
+            AiNftError::PendingMessages
+        );
+        (
+            ai_character.character_nft_mint,
+            ai_character.compute_token_account,
+            ai_character.bump,
+            ai_character.name,
+        )
+    };
+    let ai_character_seeds = &[
+        "ainft".as_bytes(),
+        character_nft_mint.as_ref(),
+        &ai_character_bump,
+    ];
+
+    let compute_swept = ctx
+        .accounts
+        .sweep_compute(compute_token_account, ai_character_seeds)?;
+    let revenue_swept = ctx.accounts.sweep_owner_revenue(ai_character_seeds)?;
+
+    // Release the name so it can be claimed again
+    CharacterName::release(
+        &ctx.accounts.character_name,
+        &ctx.accounts.ai_nft.key(),
+        &ctx.accounts.ai_character.key(),
+        &name,
+        &ctx.accounts.owner,
+    )?;
+
+    // Burns the token and closes the metadata, edition and token account.
+    // The collection size is decremented by the metadata program.
+    metadata::burn_nft(
+        ctx.accounts.burn_nft_ctx(),
+        Some(ctx.accounts.master_metadata.key()),
+    )?;
+
+    emit!(AiNftBurned {
+        ai_character: ctx.accounts.ai_character.key(),
+        collection: ctx.accounts.ai_nft.key(),
+        owner: ctx.accounts.owner.key(),
+        compute_swept,
+        revenue_swept,
+    });
+
+    Ok(())
+}
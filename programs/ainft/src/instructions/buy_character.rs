@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, Listing},
+};
+
+#[event]
+pub struct CharacterSold {
+    pub ai_character: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub payment_mint: Pubkey,
+    pub royalties: u64,
+}
+
+#[derive(Accounts)]
+pub struct BuyCharacter<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = ["listing".as_bytes(), ai_character_mint.key().as_ref()],
+        bump = listing.bump,
+        has_one = seller @ AiNftError::InvalidListing,
+        has_one = ai_character @ AiNftError::InvalidListing,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    // the ai character is sold with its config and compute token account
+    #[account(mut)]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Box<Account<'info, Mint>>,
+
+    // metadata of the agent nft, holds the royalties
+    #[account(
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            ai_character_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub ai_character_metadata: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = ai_character_mint,
+        associated_token::authority = listing,
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = ai_character_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    // payment token accounts, required if the price is paid in compute tokens
+    #[account(
+        mut,
+        constraint = buyer_payment_token_account.mint == listing.payment_mint @ AiNftError::InvalidComputeMint,
+        constraint = buyer_payment_token_account.owner == buyer.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub buyer_payment_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = seller_payment_token_account.mint == listing.payment_mint @ AiNftError::InvalidComputeMint,
+        constraint = seller_payment_token_account.owner == listing.seller @ AiNftError::InvalidTokenOwner,
+    )]
+    pub seller_payment_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    // remaining accounts: the metadata creators in order, or their payment
+    // token accounts when the price is paid in compute tokens
+}
+
+impl<'info> BuyCharacter<'info> {
+    /// Pays `amount` from the buyer in SOL or in the payment mint
+    fn pay(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if !self.listing.is_token_payment() {
+            return anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: self.buyer.to_account_info(),
+                        to,
+                    },
+                ),
+                amount,
+            );
+        }
+        let buyer_payment_token_account = self
+            .buyer_payment_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingPaymentAccounts)?;
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: buyer_payment_token_account.to_account_info(),
+                    to,
+                    authority: self.buyer.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Checks a royalty recipient passed as remaining account
+    fn check_royalty_recipient(
+        &self,
+        recipient: &'info AccountInfo<'info>,
+        creator: Pubkey,
+    ) -> Result<()> {
+        if !self.listing.is_token_payment() {
+            require_keys_eq!(
+                recipient.key(),
+                creator,
+                AiNftError::InvalidRoyaltyRecipients
+            );
+            return Ok(());
+        }
+        let token_account = Account::<TokenAccount>::try_from(recipient)?;
+        require_keys_eq!(
+            token_account.owner,
+            creator,
+            AiNftError::InvalidRoyaltyRecipients
+        );
+        require_keys_eq!(
+            token_account.mint,
+            self.listing.payment_mint,
+            AiNftError::InvalidRoyaltyRecipients
+        );
+        Ok(())
+    }
+}
+
+pub fn buy_character_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyCharacter<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let listing = &accounts.listing;
+
+    // Royalties as configured in the metadata
+    let metadata = &accounts.ai_character_metadata;
+    let creators: Vec<(Pubkey, u8)> = metadata
+        .creators
+        .iter()
+        .flatten()
+        .map(|creator| (creator.address, creator.share))
+        .collect();
+    let (royalties, seller_amount) =
+        listing.split_sale(metadata.seller_fee_basis_points, &creators);
+    require!(
+        ctx.remaining_accounts.len() == royalties.len(),
+        AiNftError::InvalidRoyaltyRecipients
+    );
+
+    let mut paid_royalties = 0;
+    for ((creator, amount), recipient) in royalties.into_iter().zip(ctx.remaining_accounts.iter()) {
+        accounts.check_royalty_recipient(recipient, creator)?;
+        accounts.pay(recipient.clone(), amount)?;
+        paid_royalties += amount;
+    }
+
+    // Pay the seller
+    let seller = if listing.is_token_payment() {
+        accounts
+            .seller_payment_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingPaymentAccounts)?
+            .to_account_info()
+    } else {
+        accounts.seller.to_account_info()
+    };
+    accounts.pay(seller, seller_amount)?;
+
+    // Release the agent nft to the buyer and close the escrow
+    token::transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.escrow_token_account.to_account_info(),
+                to: accounts.buyer_ai_character_token_account.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+        )
+        .with_signer(&[&listing.as_seeds()]),
+        1,
+    )?;
+    token::close_account(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: accounts.escrow_token_account.to_account_info(),
+                destination: accounts.seller.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+        )
+        .with_signer(&[&listing.as_seeds()]),
+    )?;
+
+    // The buyer becomes the owner of the ai character
+    accounts
+        .ai_character
+        .load_mut()?
+        .set_owner(accounts.buyer.key(), Clock::get()?.unix_timestamp);
+
+    emit!(CharacterSold {
+        ai_character: listing.ai_character,
+        seller: listing.seller,
+        buyer: accounts.buyer.key(),
+        price: listing.price,
+        payment_mint: listing.payment_mint,
+        royalties: paid_royalties,
+    });
+
+    Ok(())
+}
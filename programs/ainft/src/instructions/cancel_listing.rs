@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{error::AiNftError, state::Listing};
+
+#[event]
+pub struct ListingCancelled {
+    pub ai_character: Pubkey,
+    pub seller: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = ["listing".as_bytes(), ai_character_mint.key().as_ref()],
+        bump = listing.bump,
+        has_one = seller @ AiNftError::InvalidOwner,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = ai_character_mint,
+        associated_token::authority = listing,
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = ai_character_mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn cancel_listing_handler(ctx: Context<CancelListing>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+
+    // Return the agent nft and close the escrow
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx
+                    .accounts
+                    .seller_ai_character_token_account
+                    .to_account_info(),
+                authority: listing.to_account_info(),
+            },
+        )
+        .with_signer(&[&listing.as_seeds()]),
+        1,
+    )?;
+    token::close_account(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.seller.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+        )
+        .with_signer(&[&listing.as_seeds()]),
+    )?;
+
+    emit!(ListingCancelled {
+        ai_character: listing.ai_character,
+        seller: listing.seller,
+    });
+
+    Ok(())
+}
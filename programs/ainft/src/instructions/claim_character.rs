@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, AiNft},
+};
+
+#[event]
+pub struct CharacterClaimed {
+    pub ai_character: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+    pub owner_since: i64,
+    pub owner_state_reset: bool,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCharacter<'info> {
+    // app ai nft account
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        mut,
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+}
+
+pub fn claim_character_handler(
+    ctx: Context<ClaimCharacter>,
+    reset_owner_state: bool,
+) -> Result<()> {
+    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
+    let previous_owner = ai_character.owner;
+    ai_character.claim(
+        ctx.accounts.authority.key(),
+        Clock::get()?.unix_timestamp,
+        reset_owner_state,
+    )?;
+
+    emit!(CharacterClaimed {
+        ai_character: ctx.accounts.ai_character.key(),
+        previous_owner,
+        owner: ai_character.owner,
+        owner_since: ai_character.owner_since,
+        owner_state_reset: reset_owner_state,
+    });
+
+    Ok(())
+}
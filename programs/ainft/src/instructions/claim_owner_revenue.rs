@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, AiNft, Lease},
+};
+
+#[event]
+pub struct OwnerRevenueClaimed {
+    pub ai_character: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct ClaimOwnerRevenue<'info> {
+    // app ai nft account
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    // token account holding the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = holder_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // the token accounts should contain the agent nft
+        constraint = holder_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+        // the revenue goes to the holder, or to the renter of an active lease
+        constraint = Lease::revenue_recipient(&lease, holder_ai_character_token_account.owner) == authority.key() @ AiNftError::InvalidOwner,
+    )]
+    pub holder_ai_character_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = ["owner_revenue".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub owner_revenue_token_account: Account<'info, TokenAccount>,
+
+    // compute token account receiving the revenue
+    #[account(
+        mut,
+        constraint = authority_compute_token_account.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = authority_compute_token_account.owner == authority.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub authority_compute_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the accrued owner revenue out to `recipient_compute_token_account`.
+/// Called when the revenue recipient changes, so the revenue accrued so far goes
+/// to the previous recipient. Returns the swept amount.
+pub fn sweep_owner_revenue<'info>(
+    token_program: &Program<'info, Token>,
+    ai_character: &AccountLoader<'info, AiCharacterNFT>,
+    owner_revenue_token_account: &AccountInfo<'info>,
+    recipient_compute_token_account: Option<&Account<'info, TokenAccount>>,
+) -> Result<u64> {
+    // the revenue account is only created once a markup is set
+    if owner_revenue_token_account.owner != &token::ID
+        || owner_revenue_token_account.data_is_empty()
+    {
+        return Ok(0);
+    }
+    let amount =
+        TokenAccount::try_deserialize(&mut &owner_revenue_token_account.try_borrow_data()?[..])?
+            .amount;
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let recipient_compute_token_account =
+        recipient_compute_token_account.ok_or(AiNftError::MissingComputeTokenAccount)?;
+    let character = ai_character.load()?;
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: owner_revenue_token_account.clone(),
+                to: recipient_compute_token_account.to_account_info(),
+                authority: ai_character.to_account_info(),
+            },
+        )
+        .with_signer(&[&character.as_seeds()]),
+        amount,
+    )?;
+
+    Ok(amount)
+}
+
+pub fn claim_owner_revenue_handler(ctx: Context<ClaimOwnerRevenue>) -> Result<()> {
+    let amount = ctx.accounts.owner_revenue_token_account.amount;
+    require!(amount > 0, AiNftError::InsufficientCompute);
+
+    // The revenue accrued by the renter is swept to them when the lease is closed
+    if let Some(lease) = Lease::load(&ctx.accounts.lease) {
+        require!(
+            !lease.is_rented() || lease.is_active(Clock::get()?.unix_timestamp),
+            AiNftError::LeaseNotClosed
+        );
+    }
+
+    let ai_character = ctx.accounts.ai_character.load()?;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_revenue_token_account.to_account_info(),
+                to: ctx.accounts.authority_compute_token_account.to_account_info(),
+                authority: ctx.accounts.ai_character.to_account_info(),
+            },
+        )
+        .with_signer(&[&ai_character.as_seeds()]),
+        amount,
+    )?;
+
+    emit!(OwnerRevenueClaimed {
+        ai_character: ctx.accounts.ai_character.key(),
+        recipient: ctx.accounts.authority.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, Lease},
+};
+
+use super::claim_owner_revenue::sweep_owner_revenue;
+
+#[event]
+pub struct LeaseClosed {
+    pub ai_character: Pubkey,
+    pub lessor: Pubkey,
+    pub renter: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct CloseLease<'info> {
+    // the lease can be closed before it is rented or once it expired, by the
+    // lessor or by the current holder of the agent nft. The rent goes back to
+    // the lessor.
+    #[account(
+        mut,
+        close = lessor,
+        seeds = ["lease".as_bytes(), lease.ai_character.as_ref()],
+        bump = lease.bump,
+        has_one = lessor @ AiNftError::InvalidOwner,
+        constraint = !lease.is_active(Clock::get()?.unix_timestamp) @ AiNftError::LeaseActive,
+    )]
+    pub lease: Box<Account<'info, Lease>>,
+
+    #[account(address = lease.ai_character @ AiNftError::InvalidLease)]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // owner revenue accrued during the lease goes to the renter
+    /// CHECK: owner revenue account of the ai character, it may not exist
+    #[account(
+        mut,
+        seeds = ["owner_revenue".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub owner_revenue_token_account: UncheckedAccount<'info>,
+
+    // compute token account of the renter, required if owner revenue accrued
+    #[account(
+        mut,
+        constraint = renter_compute_token_account.owner == lease.renter @ AiNftError::InvalidTokenOwner,
+    )]
+    pub renter_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub lessor: SystemAccount<'info>,
+
+    // token account of the agent nft, required if the authority isn't the lessor
+    #[account(
+        constraint = authority_ai_character_token_account.mint == ai_character.load().unwrap().character_nft_mint @ AiNftError::InvalidAgentNftMint,
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub authority_ai_character_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn close_lease_handler(ctx: Context<CloseLease>) -> Result<()> {
+    // A lease left behind by a previous holder can be closed by the new one
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.lease.lessor
+            || ctx.accounts.authority_ai_character_token_account.is_some(),
+        AiNftError::InvalidOwner
+    );
+
+    // The revenue goes back to the holder from now on
+    if ctx.accounts.lease.is_rented() {
+        sweep_owner_revenue(
+            &ctx.accounts.token_program,
+            &ctx.accounts.ai_character,
+            &ctx.accounts.owner_revenue_token_account,
+            ctx.accounts.renter_compute_token_account.as_deref(),
+        )?;
+    }
+
+    emit!(LeaseClosed {
+        ai_character: ctx.accounts.lease.ai_character,
+        lessor: ctx.accounts.lease.lessor,
+        renter: ctx.accounts.lease.renter,
+    });
+
+    Ok(())
+}
@@ -19,13 +19,20 @@ pub struct AiCharacterComputeAccountCreated {
 pub struct CreateAiCharacterComputeAccount<'info> {
     #[account(
         mut,
-        seeds = ["app_ainft".as_bytes()],
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
         bump = ai_nft.bump[0],
         constraint = ai_nft.compute_mint != Pubkey::default() @ AiNftError::ComputeMintNotInitialized
     )]
     pub ai_nft: Box<Account<'info, AiNft>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
     pub ai_character: AccountLoader<'info, AiCharacterNFT>,
 
     /// The compute mint of the AI NFT
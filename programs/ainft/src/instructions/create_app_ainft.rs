@@ -6,8 +6,8 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
         self,
-        mpl_token_metadata::types::{Creator, DataV2},
-        SignMetadata, UpdatePrimarySaleHappenedViaToken,
+        mpl_token_metadata::types::{CollectionDetails, Creator, DataV2},
+        CreateMasterEditionV3, SignMetadata, UpdatePrimarySaleHappenedViaToken,
     },
     token::{self, FreezeAccount, MintTo, Token},
 };
@@ -27,7 +27,11 @@ pub struct CreateAppAiNft<'info> {
         init,
         payer = payer,
         space = AiNft::LEN,
-        seeds = ["app_ainft".as_bytes()],
+        seeds = [
+            "app_ainft".as_bytes(),
+            payer.key().as_ref(),
+            params.collection_id.to_le_bytes().as_ref(),
+        ],
         bump
     )]
     pub ai_nft: Box<Account<'info, AiNft>>,
@@ -67,6 +71,20 @@ pub struct CreateAppAiNft<'info> {
     )]
     pub master_metadata: UncheckedAccount<'info>,
 
+    /// CHECK: Account allocation and initialization is done via CPI to the metadata program.
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            master_mint.key().as_ref(),
+            "edition".as_bytes(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -94,6 +112,24 @@ impl<'info> CreateAppAiNft<'info> {
         CpiContext::new(program, accounts)
     }
 
+    pub fn create_master_edition_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, CreateMasterEditionV3<'info>> {
+        let program = self.metadata_program.to_account_info();
+        let accounts = CreateMasterEditionV3 {
+            edition: self.master_edition.to_account_info(),
+            mint: self.master_mint.to_account_info(),
+            update_authority: self.ai_nft.to_account_info(),
+            mint_authority: self.ai_nft.to_account_info(),
+            payer: self.payer.to_account_info(),
+            metadata: self.master_metadata.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+        CpiContext::new(program, accounts)
+    }
+
     pub fn freeze_account_ctx(&self) -> CpiContext<'_, '_, '_, 'info, FreezeAccount<'info>> {
         let program = self.token_program.to_account_info();
         let accounts = FreezeAccount {
@@ -147,6 +183,7 @@ pub fn create_app_ainft_handler(
     if params.mint_price == 0 {
         return err!(AiNftError::InvalidMintPrice);
     }
+    params.royalties.validate()?;
     initialize_master_token(&ctx, &params)?;
     create_and_sign_metadata(&ctx, &params)?;
 
@@ -162,6 +199,7 @@ pub fn create_app_ainft_handler(
     emit!(CollectionCreated {
         collection: ainft.key(),
         authority: ainft.authority,
+        collection_id: ainft.collection_id,
     });
 
     Ok(())
@@ -170,9 +208,16 @@ pub fn create_app_ainft_handler(
 #[inline(never)]
 fn initialize_master_token(
     ctx: &Context<CreateAppAiNft>,
-    _params: &CreateAiNftParams,
+    params: &CreateAiNftParams,
 ) -> Result<()> {
-    let ainft_seeds = &["app_ainft".as_bytes(), &[ctx.bumps.ai_nft]];
+    let payer = ctx.accounts.payer.key();
+    let collection_id = params.collection_id.to_le_bytes();
+    let ainft_seeds = &[
+        "app_ainft".as_bytes(),
+        payer.as_ref(),
+        collection_id.as_ref(),
+        &[ctx.bumps.ai_nft],
+    ];
 
     // Mint the master token
     token::mint_to(ctx.accounts.mint_to_ctx().with_signer(&[ainft_seeds]), 1)?;
@@ -192,7 +237,14 @@ fn create_and_sign_metadata(
     ctx: &Context<CreateAppAiNft>,
     params: &CreateAiNftParams,
 ) -> Result<()> {
-    let ainft_seeds = &["app_ainft".as_bytes(), &[ctx.bumps.ai_nft]];
+    let payer = ctx.accounts.payer.key();
+    let collection_id = params.collection_id.to_le_bytes();
+    let ainft_seeds = &[
+        "app_ainft".as_bytes(),
+        payer.as_ref(),
+        collection_id.as_ref(),
+        &[ctx.bumps.ai_nft],
+    ];
 
     let creator = Creator {
         address: ctx.accounts.payer.key(),
@@ -215,7 +267,8 @@ fn create_and_sign_metadata(
         },
         true, // is_mutable
         true, // update_authority_is_signer
-        None,
+        // sized collection, the size is tracked by the metadata program
+        Some(CollectionDetails::V1 { size: 0 }),
     )?;
 
     // Sign metadata and update primary sale
@@ -224,5 +277,13 @@ fn create_and_sign_metadata(
         ctx.accounts.update_primary_sale_happened_ctx(),
     )?;
 
+    // The master edition makes the collection NFT a one of one
+    metadata::create_master_edition_v3(
+        ctx.accounts
+            .create_master_edition_ctx()
+            .with_signer(&[ainft_seeds]),
+        Some(0),
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, AiCharacterNFT, AiNft, Lease, ProtocolConfig},
+};
+
+#[event]
+pub struct CharacterComputeMintCreated {
+    pub ai_character: Pubkey,
+    pub compute_mint: Pubkey,
+    pub compute_token_account: Pubkey,
+    pub compute_swept: u64,
+}
+
+#[derive(Accounts)]
+pub struct CreateCharacterComputeMint<'info> {
+    // app ai nft account
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    #[account(
+        mut,
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+        // holder only settings can't change while the character is rented out
+        constraint = Lease::load_active(&lease).is_none() @ AiNftError::LeaseActive,
+    )]
+    pub authority_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    // compute mint of the character, minted by the ai character
+    #[account(
+        init,
+        payer = authority,
+        seeds = ["character_compute_mint".as_bytes(), ai_character.key().as_ref()],
+        bump,
+        mint::decimals = 9,
+        mint::authority = ai_character,
+    )]
+    pub character_compute_mint: Box<Account<'info, Mint>>,
+
+    // new compute token account of the ai character
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = character_compute_mint,
+        associated_token::authority = ai_character,
+    )]
+    pub character_compute_token_account: Box<Account<'info, TokenAccount>>,
+
+    // collection compute token account of the ai character, required if it was created
+    #[account(
+        mut,
+        constraint = ai_character_compute_token_account.key() == ai_character.load().unwrap().compute_token_account @ AiNftError::InvalidComputeVault,
+    )]
+    pub ai_character_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // receives the remaining collection compute tokens of the ai character
+    #[account(
+        mut,
+        constraint = authority_compute_token_account.owner == authority.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub authority_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCharacterComputeMint<'info> {
+    /// Moves the remaining collection compute tokens of the ai character to the
+    /// holder, they can't be spent once the character is paid in its own mint.
+    /// Returns the amount of swept compute tokens.
+    fn sweep_compute(&self, compute_token_account: Pubkey, seeds: &[&[u8]]) -> Result<u64> {
+        if compute_token_account == Pubkey::default() {
+            return Ok(0);
+        }
+        let ai_character_compute_token_account = self
+            .ai_character_compute_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingComputeTokenAccount)?;
+
+        let amount = ai_character_compute_token_account.amount;
+        if amount > 0 {
+            let authority_compute_token_account = self
+                .authority_compute_token_account
+                .as_ref()
+                .ok_or(AiNftError::MissingComputeTokenAccount)?;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: ai_character_compute_token_account.to_account_info(),
+                        to: authority_compute_token_account.to_account_info(),
+                        authority: self.ai_character.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
+        }
+        Ok(amount)
+    }
+}
+
+pub fn create_character_compute_mint_handler(
+    ctx: Context<CreateCharacterComputeMint>,
+) -> Result<()> {
+    let (character_nft_mint, compute_token_account, ai_character_bump) = {
+        let mut ai_character = ctx.accounts.ai_character.load_mut()?;
+        let previous_compute_token_account = ai_character.compute_token_account;
+        ai_character.set_compute_mint(
+            ctx.accounts.character_compute_mint.key(),
+            ctx.accounts.character_compute_token_account.key(),
+        )?;
+        (
+            ai_character.character_nft_mint,
+            previous_compute_token_account,
+            ai_character.bump,
+        )
+    };
+    let ai_character_seeds = &[
+        "ainft".as_bytes(),
+        character_nft_mint.as_ref(),
+        &ai_character_bump,
+    ];
+
+    let compute_swept = ctx
+        .accounts
+        .sweep_compute(compute_token_account, ai_character_seeds)?;
+
+    emit!(CharacterComputeMintCreated {
+        ai_character: ctx.accounts.ai_character.key(),
+        compute_mint: ctx.accounts.character_compute_mint.key(),
+        compute_token_account: ctx.accounts.character_compute_token_account.key(),
+        compute_swept,
+    });
+
+    Ok(())
+}
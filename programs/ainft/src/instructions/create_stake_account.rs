@@ -20,7 +20,11 @@ pub struct StakeAccountCreated {
 pub struct CreateStakeAccount<'info> {
     #[account(
         mut,
-        seeds = ["app_ainft".as_bytes()],
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
         bump = ai_nft.bump[0],
         constraint = ai_nft.compute_mint != Pubkey::default() @ AiNftError::ComputeMintNotInitialized
     )]
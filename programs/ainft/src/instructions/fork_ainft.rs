@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, Lease},
+};
+
+use super::mint_ainft::*;
+
+#[event]
+pub struct AiNftForked {
+    pub ai_nft: Pubkey,
+    pub parent: Pubkey,
+    pub owner: Pubkey,
+    pub fork_fee: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ForkAiNft<'info> {
+    pub mint: MintAiNft<'info>,
+
+    // the ai character the fork is copied from
+    #[account(
+        constraint = parent_ai_character.load().unwrap().app_ai_nft_mint == mint.ai_nft.master_mint @ AiNftError::InvalidAiNft,
+        constraint = parent_ai_character.load().unwrap().is_fork_allowed() @ AiNftError::ForkNotAllowed,
+    )]
+    pub parent_ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // token account holding the parent nft, its owner receives the fork fee
+    #[account(
+        constraint = parent_owner_ai_character_token_account.mint == parent_ai_character.load().unwrap().character_nft_mint @ AiNftError::InvalidAgentNftMint,
+        constraint = parent_owner_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub parent_owner_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Lease PDA of the parent ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), parent_ai_character.key().as_ref()], bump)]
+    pub parent_lease: UncheckedAccount<'info>,
+
+    // compute token account of the parent owner, or of its renter during a lease.
+    // Required if there is a fork fee
+    #[account(
+        mut,
+        constraint = parent_owner_compute_token_account.mint == mint.ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = parent_owner_compute_token_account.owner == Lease::revenue_recipient(&parent_lease, parent_owner_ai_character_token_account.owner) @ AiNftError::InvalidTokenOwner,
+    )]
+    pub parent_owner_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // the payer's compute token account, required if there is a fork fee
+    #[account(
+        mut,
+        constraint = payer_compute_token_account.mint == mint.ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = payer_compute_token_account.owner == payer.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub payer_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = payer.key() == mint.payer.key() @ AiNftError::InvalidOwner,
+    )]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn fork_ainft_handler(
+    ctx: Context<ForkAiNft>,
+    name: String,
+    uri: String,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let parent_key = ctx.accounts.parent_ai_character.key();
+    let parent = ctx.accounts.parent_ai_character.load()?;
+    let fork_fee = parent.fork_fee;
+
+    // Pay the fork fee to the owner, or the renter, of the parent character
+    if fork_fee > 0 {
+        let (Some(payer_compute_token_account), Some(parent_owner_compute_token_account)) = (
+            &ctx.accounts.payer_compute_token_account,
+            &ctx.accounts.parent_owner_compute_token_account,
+        ) else {
+            return err!(AiNftError::MissingComputeTokenAccount);
+        };
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: payer_compute_token_account.to_account_info(),
+                    to: parent_owner_compute_token_account.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            fork_fee,
+        )?;
+    }
+
+    ctx.accounts.mint.process(
+        &ctx.bumps.mint,
+        &allowlist_proof,
+        &name,
+        &uri,
+        |ai_character| {
+            ai_character.fork_from(parent_key, &parent);
+            Ok(())
+        },
+    )?;
+
+    emit!(AiNftForked {
+        ai_nft: ctx.accounts.mint.ai_character.key(),
+        parent: parent_key,
+        owner: ctx.accounts.payer.key(),
+        fork_fee,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AiNftError, program::Ainft, state::ProtocolConfig};
+
+#[event]
+pub struct ProtocolConfigInitialized {
+    pub protocol_config: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolConfig::LEN,
+        seeds = ["protocol_config".as_bytes()],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    // only the upgrade authority of the program can create the protocol config
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ AiNftError::InvalidAuthority)]
+    pub program: Program<'info, Ainft>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ AiNftError::InvalidAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_protocol_config_handler(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    **protocol_config = ProtocolConfig::new(
+        ctx.accounts.authority.key(),
+        ctx.bumps.protocol_config,
+    );
+
+    emit!(ProtocolConfigInitialized {
+        protocol_config: protocol_config.key(),
+        admin: protocol_config.admin,
+    });
+
+    Ok(())
+}
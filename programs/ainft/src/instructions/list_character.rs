@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, AiNft, Lease, Listing},
+};
+
+use super::claim_owner_revenue::sweep_owner_revenue;
+
+#[event]
+pub struct CharacterListed {
+    pub ai_character: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub payment_mint: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ListCharacter<'info> {
+    // app ai nft account
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    #[account(
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = Listing::LEN,
+        seeds = ["listing".as_bytes(), ai_character_mint.key().as_ref()],
+        bump,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    // token account of the seller holding the agent nft
+    #[account(
+        mut,
+        constraint = seller_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = seller_ai_character_token_account.owner == seller.key() @ AiNftError::InvalidOwner,
+        constraint = seller_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub seller_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    // escrow holding the agent nft while listed
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = ai_character_mint,
+        associated_token::authority = listing,
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist. A rented character can't be listed.
+    #[account(
+        seeds = ["lease".as_bytes(), ai_character.key().as_ref()],
+        bump,
+        constraint = Lease::load_active(&lease).is_none() @ AiNftError::LeaseActive,
+    )]
+    pub lease: UncheckedAccount<'info>,
+
+    /// CHECK: owner revenue account of the ai character, it may not exist
+    #[account(
+        mut,
+        seeds = ["owner_revenue".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub owner_revenue_token_account: UncheckedAccount<'info>,
+
+    // compute token account of the seller, required if owner revenue accrued
+    #[account(
+        mut,
+        constraint = seller_compute_token_account.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = seller_compute_token_account.owner == seller.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub seller_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn list_character_handler(
+    ctx: Context<ListCharacter>,
+    price: u64,
+    pay_in_compute: bool,
+) -> Result<()> {
+    require!(price > 0, AiNftError::InvalidListing);
+    let payment_mint = if pay_in_compute {
+        ctx.accounts.ai_nft.check_compute_mint_initialized()?;
+        ctx.accounts.ai_nft.compute_mint
+    } else {
+        Pubkey::default()
+    };
+
+    // The revenue goes to the buyer once sold, pay out what the seller accrued so
+    // far. Revenue of an expired lease stays with the renter until it is closed.
+    if !Lease::is_rented_out(&ctx.accounts.lease) {
+        sweep_owner_revenue(
+            &ctx.accounts.token_program,
+            &ctx.accounts.ai_character,
+            &ctx.accounts.owner_revenue_token_account,
+            ctx.accounts.seller_compute_token_account.as_deref(),
+        )?;
+    }
+
+    // Move the agent nft into escrow
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx
+                    .accounts
+                    .seller_ai_character_token_account
+                    .to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    let listing = &mut ctx.accounts.listing;
+    listing.seller = ctx.accounts.seller.key();
+    listing.ai_character = ctx.accounts.ai_character.key();
+    listing.ai_character_mint = ctx.accounts.ai_character_mint.key();
+    listing.price = price;
+    listing.payment_mint = payment_mint;
+    listing.created_at = Clock::get()?.unix_timestamp;
+    listing.bump = ctx.bumps.listing;
+
+    emit!(CharacterListed {
+        ai_character: listing.ai_character,
+        seller: listing.seller,
+        price,
+        payment_mint,
+    });
+
+    Ok(())
+}
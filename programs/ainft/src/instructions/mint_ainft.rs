@@ -1,19 +1,30 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::metadata::mpl_token_metadata::types::{Collection, DataV2};
-use anchor_spl::metadata::{self, CreateMetadataAccountsV3, Metadata};
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::metadata::mpl_token_metadata::types::{Collection, Creator, DataV2};
+use anchor_spl::metadata::{
+    self, CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata, SignMetadata,
+    VerifySizedCollectionItem,
+};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
 use std::mem::size_of;
 use crate::error::AiNftError;
-use crate::state::{ AiCharacterNFT, AiNft};
+use crate::events::AiNftMinted;
+use crate::state::{
+    pause_flags, AiCharacterNFT, AiNft, CharacterName, CharacterTemplate, ProtocolConfig,
+    WalletMintCount,
+};
 
 #[derive(Accounts)]
 #[instruction(name: String)]
 pub struct MintAiNft<'info> {
     #[account(
         mut,
-        seeds = ["app_ainft".as_bytes()],
-        bump,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
     )]
     pub ai_nft: Box<Account<'info, AiNft>>,
 
@@ -31,7 +42,12 @@ pub struct MintAiNft<'info> {
     #[account(  
         init,
         payer = payer,
-        seeds = ["mint".as_bytes(), ai_nft.key().as_ref(), name.as_bytes()],
+        // seeded by the mint index so a released name can be minted again
+        seeds = [
+            "mint".as_bytes(),
+            ai_nft.key().as_ref(),
+            ai_nft.mint_count.to_le_bytes().as_ref(),
+        ],
         bump,
         mint::decimals = 0,
         mint::authority = ai_nft,
@@ -39,13 +55,88 @@ pub struct MintAiNft<'info> {
     )]
     pub ai_character_mint: Box<Account<'info, Mint>>,
 
+    // registry entry keeping the character name unique in the collection
+    #[account(
+        init,
+        payer = payer,
+        space = CharacterName::LEN,
+        seeds = ["character_name".as_bytes(), ai_nft.key().as_ref(), name.as_bytes()],
+        bump,
+    )]
+    pub character_name: Box<Account<'info, CharacterName>>,
+
+    /// CHECK: collection template the ai character config starts from. Always required
+    /// so the minter can't skip it, it is only loaded once the collection created it.
+    #[account(
+        seeds = ["character_template".as_bytes(), ai_nft.key().as_ref()],
+        bump,
+    )]
+    pub character_template: UncheckedAccount<'info>,
+
     /// CHECK: Account allocation and initialization is done via CPI to the metadata program.
     #[account(mut)]
     pub ai_character_metadata: UncheckedAccount<'info>,
 
+    /// CHECK: Account allocation and initialization is done via CPI to the metadata program.
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            ai_character_mint.key().as_ref(),
+            "edition".as_bytes(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub ai_character_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Collection mint, checked against the ai nft account
+    #[account(address = ai_nft.master_mint @ AiNftError::InvalidCollection)]
+    pub master_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Collection metadata, checked against the ai nft account. The collection size is updated via CPI to the metadata program.
+    #[account(
+        mut,
+        address = ai_nft.master_metadata @ AiNftError::InvalidCollection,
+    )]
+    pub master_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition, checked through the seeds
+    #[account(
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            master_mint.key().as_ref(),
+            "edition".as_bytes(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::MINTING) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    // number of characters the payer minted in each mint phase, required if
+    // the active phase has a per wallet limit
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = WalletMintCount::LEN,
+        seeds = ["wallet_mints".as_bytes(), ai_nft.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub wallet_mint_count: Option<Box<Account<'info, WalletMintCount>>>,
+
     // payer token account
     #[account(
         init_if_needed,
@@ -55,6 +146,26 @@ pub struct MintAiNft<'info> {
     )]
     pub payer_ai_character_token_account: Box<Account<'info, TokenAccount>>,
 
+    // token the mint price is paid in, required if the collection has a payment mint
+    #[account(address = ai_nft.payment_mint @ AiNftError::InvalidPaymentMint)]
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = payer,
+    )]
+    pub payer_payment_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // collection treasury holding the mint proceeds paid in tokens
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = ai_nft,
+    )]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -89,6 +200,107 @@ impl<'info> MintAiNft<'info> {
         CpiContext::new(program, accounts)
     }
 
+    pub fn create_master_edition_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, CreateMasterEditionV3<'info>> {
+        let program = self.metadata_program.to_account_info();
+        let accounts = CreateMasterEditionV3 {
+            edition: self.ai_character_master_edition.to_account_info(),
+            mint: self.ai_character_mint.to_account_info(),
+            update_authority: self.ai_nft.to_account_info(),
+            mint_authority: self.ai_nft.to_account_info(),
+            payer: self.payer.to_account_info(),
+            metadata: self.ai_character_metadata.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+        CpiContext::new(program, accounts)
+    }
+
+    pub fn verify_collection_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, VerifySizedCollectionItem<'info>> {
+        let program = self.metadata_program.to_account_info();
+        let accounts = VerifySizedCollectionItem {
+            payer: self.payer.to_account_info(),
+            metadata: self.ai_character_metadata.to_account_info(),
+            collection_authority: self.ai_nft.to_account_info(),
+            collection_mint: self.master_mint.to_account_info(),
+            collection_metadata: self.master_metadata.to_account_info(),
+            collection_master_edition: self.master_edition.to_account_info(),
+        };
+        CpiContext::new(program, accounts)
+    }
+
+    /// Checks the active mint phase and records the mint for the payer.
+    /// Returns the price to pay.
+    #[inline(never)]
+    fn process_mint_phase(
+        &mut self,
+        wallet_mint_count_bump: Option<u8>,
+        allowlist_proof: &[[u8; 32]],
+    ) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        let Some(phase_index) = self.ai_nft.active_mint_phase(now)? else {
+            return Ok(self.ai_nft.mint_price);
+        };
+        let phase = &self.ai_nft.mint_phases[phase_index];
+        phase.verify_allowlist(&self.payer.key(), allowlist_proof)?;
+        if phase.max_per_wallet == 0 {
+            return Ok(phase.price);
+        }
+
+        let (Some(wallet_mint_count), Some(wallet_mint_count_bump)) =
+            (&mut self.wallet_mint_count, wallet_mint_count_bump)
+        else {
+            return err!(AiNftError::MissingWalletMintCount);
+        };
+        wallet_mint_count.ai_nft = self.ai_nft.key();
+        wallet_mint_count.wallet = self.payer.key();
+        wallet_mint_count.bump = wallet_mint_count_bump;
+        wallet_mint_count.record_mint(self.ai_nft.mint_phases_version, phase_index, phase)?;
+
+        Ok(phase.price)
+    }
+
+    #[inline(never)]
+    fn process_payment(&self, price: u64) -> Result<()> {
+        if price == 0 {
+            return Ok(());
+        }
+
+        if !self.ai_nft.is_token_payment() {
+            // Transfer SOL for mint price
+            let cpi_context = CpiContext::new(
+                self.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: self.payer.to_account_info(),
+                    to: self.ai_nft.to_account_info(),
+                },
+            );
+            return anchor_lang::system_program::transfer(cpi_context, price);
+        }
+
+        // Transfer payment tokens into the collection treasury
+        let (Some(_), Some(payer_payment_token_account), Some(treasury_token_account)) = (
+            &self.payment_mint,
+            &self.payer_payment_token_account,
+            &self.treasury_token_account,
+        ) else {
+            return err!(AiNftError::MissingPaymentAccounts);
+        };
+        let cpi_context = CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: payer_payment_token_account.to_account_info(),
+                to: treasury_token_account.to_account_info(),
+                authority: self.payer.to_account_info(),
+            },
+        );
+        token::transfer(cpi_context, price)
+    }
+
     #[inline(never)]
     fn process_mint(&self) -> Result<()> {
         // Mint logic
@@ -98,6 +310,38 @@ impl<'info> MintAiNft<'info> {
         )
     }
 
+    /// Metadata creators from the collection royalties, with the minter as a
+    /// creator if it has a share. Only the update authority can be verified at
+    /// creation, the minter verifies itself with `process_sign_metadata`.
+    fn metadata_creators(&self) -> Result<Option<Vec<Creator>>> {
+        let royalties = &self.ai_nft.royalties;
+        let mut creators: Vec<Creator> = royalties
+            .creators
+            .iter()
+            .map(|creator| Creator {
+                address: creator.address,
+                verified: false,
+                share: creator.share,
+            })
+            .collect();
+        if royalties.minter_share > 0 {
+            require!(
+                !creators.iter().any(|creator| creator.address == self.payer.key()),
+                AiNftError::InvalidRoyalties
+            );
+            creators.push(Creator {
+                address: self.payer.key(),
+                verified: false,
+                share: royalties.minter_share,
+            });
+        }
+        if creators.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(creators))
+        }
+    }
+
     #[inline(never)]
     fn process_metadata(&self, name: &str, uri: &str) -> Result<()> {
         // Metadata creation logic
@@ -108,8 +352,8 @@ impl<'info> MintAiNft<'info> {
                 name: name.to_string(),
                 symbol: "AINFT".to_string(),
                 uri: uri.to_string(),
-                seller_fee_basis_points: 0,
-                creators: None,
+                seller_fee_basis_points: self.ai_nft.royalties.seller_fee_basis_points,
+                creators: self.metadata_creators()?,
                 collection: Some(
                     Collection {
                         verified: false,
@@ -123,6 +367,110 @@ impl<'info> MintAiNft<'info> {
             None,
         )
     }
+
+    #[inline(never)]
+    fn process_sign_metadata(&self) -> Result<()> {
+        // The minter signs the transaction, so it can verify itself as creator
+        if self.ai_nft.royalties.minter_share == 0 {
+            return Ok(());
+        }
+        metadata::sign_metadata(CpiContext::new(
+            self.metadata_program.to_account_info(),
+            SignMetadata {
+                creator: self.payer.to_account_info(),
+                metadata: self.ai_character_metadata.to_account_info(),
+            },
+        ))
+    }
+
+    #[inline(never)]
+    fn process_master_edition(&self) -> Result<()> {
+        // The master edition makes the character a one of one, the mint and
+        // freeze authority move to the edition
+        metadata::create_master_edition_v3(
+            self.create_master_edition_ctx()
+                .with_signer(&[&self.ai_nft.as_seeds()]),
+            Some(0),
+        )
+    }
+
+    #[inline(never)]
+    fn process_verify_collection(&self) -> Result<()> {
+        // Verify the ai character as a member of the sized collection
+        metadata::verify_sized_collection_item(
+            self.verify_collection_ctx()
+                .with_signer(&[&self.ai_nft.as_seeds()]),
+            None,
+        )
+    }
+
+    /// Mints the character NFT and initializes its ai character account.
+    /// `init_ai_character` runs after the default initialization and can
+    /// override the initial state of the ai character.
+    #[inline(never)]
+    pub fn process(
+        &mut self,
+        bumps: &MintAiNftBumps,
+        allowlist_proof: &[[u8; 32]],
+        name: &str,
+        uri: &str,
+        init_ai_character: impl FnOnce(&mut AiCharacterNFT) -> Result<()>,
+    ) -> Result<()> {
+        // The name seeds the name registry entry, check it before it is stored
+        CharacterName::validate(name)?;
+
+        // Verify supply
+        self.ai_nft.check_supply()?;
+
+        // Pay the price of the active mint phase
+        let price = self.process_mint_phase(bumps.wallet_mint_count, allowlist_proof)?;
+        self.process_payment(price)?;
+
+        // Process mint
+        self.process_mint()?;
+
+        // Initialize ai character 
+        {
+            let mut ai_character = self.ai_character.load_init()?;
+            ai_character.set(
+                &self.ai_nft.master_mint.key(),
+                &self.ai_character_mint.key(),
+                name,
+                &self.ai_nft.default_execution_client,
+                Pubkey::default(),
+                bumps.ai_character,
+            );
+            ai_character.set_owner(self.payer.key(), Clock::get()?.unix_timestamp);
+            // Start from the collection template, or the default config if none is set
+            ai_character.character_config =
+                CharacterTemplate::load_config(&self.character_template)?.unwrap_or_default();
+            ai_character.update_config_name(name.to_string())?;
+            init_ai_character(&mut ai_character)?;
+        }
+        // Claim the character name
+        self.character_name.claim(
+            self.ai_nft.key(),
+            self.ai_character.key(),
+            bumps.character_name,
+        );
+
+        // Process metadata
+        self.process_metadata(name, uri)?;
+        self.process_sign_metadata()?;
+        self.process_master_edition()?;
+        self.process_verify_collection()?;
+
+        // Increment collection mint count
+        self.ai_nft.mint_count += 1;
+
+        emit!(AiNftMinted {
+            ai_nft: self.ai_character.key(),
+            collection: self.ai_nft.key(),
+            owner: self.payer.key(),
+        });
+
+        Ok(())
+    }
 }
 
 #[inline(never)]
@@ -130,40 +478,13 @@ pub fn mint_ainft_handler(
     ctx: Context<MintAiNft>,
     name: String,
     uri: String,
+    allowlist_proof: Vec<[u8; 32]>,
 ) -> Result<()> {
-    // Verify supply
-    require!(ctx.accounts.ai_nft.mint_count <= ctx.accounts.ai_nft.max_supply, AiNftError::SupplyExceeded);
-
-    // transfer SOL for mint price
-    if ctx.accounts.ai_nft.mint_price > 0 {
-        // Transfer SOL for mint price
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.payer.to_account_info(),
-                to: ctx.accounts.ai_nft.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(cpi_context, ctx.accounts.ai_nft.mint_price as u64)?;
-    }
-
-    // Process mint
-    ctx.accounts.process_mint()?;
-
-    // Initialize ai character 
-    ctx.accounts.ai_character.load_init().unwrap().set(
-        &ctx.accounts.ai_nft.master_mint.key(),
-        &ctx.accounts.ai_character_mint.key(),
+    ctx.accounts.process(
+        &ctx.bumps,
+        &allowlist_proof,
         &name,
-        &ctx.accounts.ai_nft.default_execution_client,
-        Pubkey::default(),
-        ctx.bumps.ai_character,
-    );
-    // Process metadata
-    ctx.accounts.process_metadata(&name, &uri)?;
-
-    // Increment collection mint count
-    ctx.accounts.ai_nft.mint_count += 1;
-
-    Ok(())
+        &uri,
+        |_| Ok(()),
+    )
 }
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::AiNftError,
+    events::ComputeTokensTopUp,
+    state::{CharacterConfigInput, ExecutionClient},
+};
+
+use super::mint_ainft::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct MintAiNftWithConfig<'info> {
+    pub mint: MintAiNft<'info>,
+
+    /// The compute mint of the AI NFT
+    #[account(
+        constraint = compute_mint.key() == mint.ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+    )]
+    pub compute_mint: Box<Account<'info, Mint>>,
+
+    // compute token account of the new ai character
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = compute_mint,
+        associated_token::authority = mint.ai_character,
+    )]
+    pub ai_character_compute_token_account: Box<Account<'info, TokenAccount>>,
+
+    // execution client of the new ai character, the collection default is used if not set
+    #[account(
+        constraint = execution_client.ai_nft == mint.ai_nft.key() @ AiNftError::InvalidAiNft,
+        constraint = execution_client.active @ AiNftError::ExecutionClientNotActive,
+    )]
+    pub execution_client: Option<Box<Account<'info, ExecutionClient>>>,
+
+    // the payer's compute token account, required for an initial compute deposit
+    #[account(
+        mut,
+        constraint = payer_compute_token_account.mint == compute_mint.key() @ AiNftError::InvalidComputeMint,
+        constraint = payer_compute_token_account.owner == payer.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub payer_compute_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = payer.key() == mint.payer.key() @ AiNftError::InvalidOwner,
+    )]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_ainft_with_config_handler(
+    ctx: Context<MintAiNftWithConfig>,
+    name: String,
+    uri: String,
+    character_config: CharacterConfigInput,
+    initial_compute_deposit: Option<u64>,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let compute_token_account = ctx.accounts.ai_character_compute_token_account.key();
+    let execution_client = ctx
+        .accounts
+        .execution_client
+        .as_ref()
+        .map(|execution_client| execution_client.key());
+
+    ctx.accounts
+        .mint
+        .process(
+            &ctx.bumps.mint,
+            &allowlist_proof,
+            &name,
+            &uri,
+            |ai_character| {
+                ai_character.update_compute_token_account(compute_token_account);
+                if let Some(execution_client) = execution_client {
+                    ai_character.update_execution_client(execution_client);
+                }
+                ai_character.update_character_config(character_config)
+            },
+        )?;
+
+    // Fund the ai character with compute tokens
+    if let Some(amount) = initial_compute_deposit {
+        require!(amount > 0, AiNftError::InvalidComputeAmount);
+        let payer_compute_token_account = ctx
+            .accounts
+            .payer_compute_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingComputeTokenAccount)?;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: payer_compute_token_account.to_account_info(),
+                    to: ctx
+                        .accounts
+                        .ai_character_compute_token_account
+                        .to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(ComputeTokensTopUp {
+            ai_nft: ctx.accounts.mint.ai_character.key(),
+            from: payer_compute_token_account.key(),
+            to: compute_token_account,
+            amount,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, AiCharacterNFT, AiNft, ProtocolConfig},
+};
+
+#[event]
+pub struct CharacterComputeMinted {
+    pub ai_character: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct MintCharacterCompute<'info> {
+    // app ai nft account
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::MINTING) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = ["character_compute_mint".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub character_compute_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == character_compute_mint.key() @ AiNftError::InvalidComputeMint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn mint_character_compute_handler(ctx: Context<MintCharacterCompute>, amount: u64) -> Result<()> {
+    let ai_character = ctx.accounts.ai_character.load()?;
+    token::mint_to(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.character_compute_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.ai_character.to_account_info(),
+            },
+        )
+        .with_signer(&[&ai_character.as_seeds()]),
+        amount,
+    )?;
+
+    emit!(CharacterComputeMinted {
+        ai_character: ctx.accounts.ai_character.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,31 +1,101 @@
+pub mod accept_authority_transfer;
+pub mod burn_ainft;
+pub mod buy_character;
+pub mod cancel_listing;
+pub mod claim_character;
+pub mod claim_owner_revenue;
+pub mod close_lease;
 pub mod create_ai_character_compute_account;
 pub mod create_app_ainft;
+pub mod create_character_compute_mint;
 pub mod create_compute_mint;
 pub mod create_stake_account;
+pub mod fork_ainft;
+pub mod freeze_character_config;
+pub mod initialize_protocol_config;
+pub mod list_character;
 pub mod mint_ainft;
+pub mod mint_ainft_with_config;
+pub mod mint_character_compute;
+pub mod offer_lease;
+pub mod propose_authority_transfer;
 pub mod register_execution_client;
+pub mod rename_character;
+pub mod rent_character;
+pub mod rollback_character_config;
 pub mod send_message;
+pub mod set_accepted_compute_mints;
 pub mod set_external_compute_mint;
+pub mod set_fork_settings;
+pub mod set_message_fee;
+pub mod set_mint_phases;
+pub mod set_owner_markup;
+pub mod set_protocol_fee;
+pub mod set_protocol_limits;
+pub mod set_protocol_pause;
+pub mod set_protocol_roles;
+pub mod set_treasury_recipients;
 pub mod stake_compute;
 pub mod unstake_compute;
 pub mod update_ai_character_execution_client;
+pub mod update_app_ainft;
 pub mod update_character_config;
 pub mod update_character_config_fields;
+pub mod update_character_metadata;
+pub mod update_character_template;
 pub mod update_execution_client_config;
+pub mod update_royalties;
+pub mod withdraw_mint_proceeds;
+pub mod withdraw_protocol_fees;
 pub mod write_response;
 
+pub use accept_authority_transfer::*;
+pub use burn_ainft::*;
+pub use buy_character::*;
+pub use cancel_listing::*;
+pub use claim_character::*;
+pub use claim_owner_revenue::*;
+pub use close_lease::*;
 pub use create_ai_character_compute_account::*;
 pub use create_app_ainft::*;
+pub use create_character_compute_mint::*;
 pub use create_compute_mint::*;
 pub use create_stake_account::*;
+pub use fork_ainft::*;
+pub use freeze_character_config::*;
+pub use initialize_protocol_config::*;
+pub use list_character::*;
 pub use mint_ainft::*;
+pub use mint_ainft_with_config::*;
+pub use mint_character_compute::*;
+pub use offer_lease::*;
+pub use propose_authority_transfer::*;
 pub use register_execution_client::*;
+pub use rename_character::*;
+pub use rent_character::*;
+pub use rollback_character_config::*;
 pub use send_message::*;
+pub use set_accepted_compute_mints::*;
 pub use set_external_compute_mint::*;
+pub use set_fork_settings::*;
+pub use set_message_fee::*;
+pub use set_mint_phases::*;
+pub use set_owner_markup::*;
+pub use set_protocol_fee::*;
+pub use set_protocol_limits::*;
+pub use set_protocol_pause::*;
+pub use set_protocol_roles::*;
+pub use set_treasury_recipients::*;
 pub use stake_compute::*;
 pub use unstake_compute::*;
 pub use update_ai_character_execution_client::*;
+pub use update_app_ainft::*;
 pub use update_character_config::*;
 pub use update_character_config_fields::*;
+pub use update_character_metadata::*;
+pub use update_character_template::*;
 pub use update_execution_client_config::*;
+pub use update_royalties::*;
+pub use withdraw_mint_proceeds::*;
+pub use withdraw_protocol_fees::*;
 pub use write_response::*;
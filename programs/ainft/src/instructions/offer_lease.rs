@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, AiNft, Lease},
+};
+
+#[event]
+pub struct LeaseOffered {
+    pub ai_character: Pubkey,
+    pub lessor: Pubkey,
+    pub rent_price: u64,
+    pub duration: i64,
+}
+
+#[derive(Accounts)]
+pub struct OfferLease<'info> {
+    // app ai nft account
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Lease::LEN,
+        seeds = ["lease".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub lease: Box<Account<'info, Lease>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn offer_lease_handler(
+    ctx: Context<OfferLease>,
+    rent_price: u64,
+    duration: i64,
+) -> Result<()> {
+    require!(duration > 0, AiNftError::InvalidLease);
+
+    let lease = &mut ctx.accounts.lease;
+    lease.ai_character = ctx.accounts.ai_character.key();
+    lease.lessor = ctx.accounts.authority.key();
+    lease.renter = Pubkey::default();
+    lease.rent_price = rent_price;
+    lease.duration = duration;
+    lease.expires_at = 0;
+    lease.bump = ctx.bumps.lease;
+
+    emit!(LeaseOffered {
+        ai_character: lease.ai_character,
+        lessor: lease.lessor,
+        rent_price,
+        duration,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AiNftError, state::AiNft};
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub ai_nft: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn propose_authority_transfer_handler(
+    ctx: Context<ProposeAuthorityTransfer>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    ai_nft.propose_authority(new_authority);
+
+    emit!(AuthorityTransferProposed {
+        ai_nft: ai_nft.key(),
+        authority: ai_nft.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use anchor_spl::{
 
 use crate::{
     error::AiNftError,
-    state::{AiNft, ExecutionClient},
+    state::{AiNft, ExecutionClient, ProtocolConfig},
 };
 
 #[event]
@@ -27,7 +27,11 @@ pub struct ExecutionClientRegistered {
 pub struct RegisterExecutionClient<'info> {
     #[account(
         mut,
-        seeds = ["app_ainft".as_bytes()],
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
         bump = ai_nft.bump[0],
         constraint = ai_nft.compute_mint != Pubkey::default() @ AiNftError::ComputeMintNotInitialized
     )]
@@ -65,6 +69,10 @@ pub struct RegisterExecutionClient<'info> {
     /// CHECK: Verified through ai_nft account constraint
     pub compute_mint: UncheckedAccount<'info>,
 
+    // protocol wide limits
+    #[account(seeds = ["protocol_config".as_bytes()], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub signer: Signer<'info>,
 
@@ -110,6 +118,7 @@ pub fn register_execution_client_handler(
         ctx.accounts.staked_token_account.key(),
         ctx.accounts.compute_token_account.key(),
         staker_fee_share,
+        ctx.accounts.protocol_config.max_gas,
     )?;
 
     Ok(())
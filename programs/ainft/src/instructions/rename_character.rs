@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{Metadata, MetadataAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{config_fields, CharacterName},
+};
+
+use super::update_character_config_fields::*;
+use super::update_character_metadata::update_character_metadata_data;
+
+#[event]
+pub struct CharacterRenamed {
+    pub ai_character: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RenameCharacter<'info> {
+    pub config: UpdateCharacterConfigField<'info>,
+
+    /// CHECK: registry entry of the current name, released by the rename. Checked in the
+    /// handler, characters minted before the registry existed have none.
+    #[account(mut)]
+    pub old_character_name: UncheckedAccount<'info>,
+
+    // registry entry of the new name, fails if the name is taken
+    #[account(
+        init,
+        payer = authority,
+        space = CharacterName::LEN,
+        seeds = ["character_name".as_bytes(), config.ai_nft.key().as_ref(), name.as_bytes()],
+        bump,
+    )]
+    pub new_character_name: Box<Account<'info, CharacterName>>,
+
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            config.ai_character_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub ai_character_metadata: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority.key() @ AiNftError::InvalidOwner,
+    )]
+    pub authority: Signer<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rename_character_handler(ctx: Context<RenameCharacter>, name: String) -> Result<()> {
+    CharacterName::validate(&name)?;
+
+    // Release the current name and claim the new one
+    let current_name = ctx.accounts.config.ai_character.load()?.name;
+    CharacterName::release(
+        &ctx.accounts.old_character_name,
+        &ctx.accounts.config.ai_nft.key(),
+        &ctx.accounts.config.ai_character.key(),
+        &current_name,
+        &ctx.accounts.authority,
+    )?;
+    ctx.accounts.new_character_name.claim(
+        ctx.accounts.config.ai_nft.key(),
+        ctx.accounts.config.ai_character.key(),
+        ctx.bumps.new_character_name,
+    );
+
+    // Checkpoint the config and update the character and config name
+    let new_name = name.clone();
+    ctx.accounts.config.apply_config_update(
+        ctx.bumps.config.config_history,
+        config_fields::NAME,
+        |ai_character| ai_character.rename(new_name),
+    )?;
+
+    update_character_metadata_data(
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.ai_character_metadata,
+        &ctx.accounts.config.ai_nft,
+        Some(&name),
+        None,
+    )?;
+
+    emit!(CharacterRenamed {
+        ai_character: ctx.accounts.config.ai_character.key(),
+        owner: ctx.accounts.authority.key(),
+        name,
+    });
+
+    Ok(())
+}
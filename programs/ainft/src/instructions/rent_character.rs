@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::AiNftError,
+    state::{AiCharacterNFT, AiNft, Lease},
+};
+
+use super::claim_owner_revenue::sweep_owner_revenue;
+
+#[event]
+pub struct CharacterRented {
+    pub ai_character: Pubkey,
+    pub lessor: Pubkey,
+    pub renter: Pubkey,
+    pub rent_price: u64,
+    pub expires_at: i64,
+}
+
+#[derive(Accounts)]
+pub struct RentCharacter<'info> {
+    // app ai nft account, the rent is paid in its compute mint
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        constraint = ai_character.key() == lease.ai_character @ AiNftError::InvalidLease,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    #[account(
+        mut,
+        seeds = ["lease".as_bytes(), lease.ai_character.as_ref()],
+        bump = lease.bump,
+        constraint = !lease.is_rented() @ AiNftError::InvalidLease,
+    )]
+    pub lease: Box<Account<'info, Lease>>,
+
+    // the lessor must still hold the agent nft
+    #[account(
+        constraint = lessor_ai_character_token_account.mint == ai_character.load().unwrap().character_nft_mint @ AiNftError::InvalidAgentNftMint,
+        constraint = lessor_ai_character_token_account.owner == lease.lessor @ AiNftError::InvalidOwner,
+        constraint = lessor_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+    )]
+    pub lessor_ai_character_token_account: Box<Account<'info, TokenAccount>>,
+
+    // compute token account of the lessor receiving the rent
+    #[account(
+        mut,
+        constraint = lessor_compute_token_account.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = lessor_compute_token_account.owner == lease.lessor @ AiNftError::InvalidTokenOwner,
+    )]
+    pub lessor_compute_token_account: Box<Account<'info, TokenAccount>>,
+
+    // owner revenue accrued before the lease goes to the lessor
+    /// CHECK: owner revenue account of the ai character, it may not exist
+    #[account(
+        mut,
+        seeds = ["owner_revenue".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub owner_revenue_token_account: UncheckedAccount<'info>,
+
+    // compute token account of the renter paying the rent
+    #[account(
+        mut,
+        constraint = renter_compute_token_account.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = renter_compute_token_account.owner == renter.key() @ AiNftError::InvalidTokenOwner,
+    )]
+    pub renter_compute_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub renter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn rent_character_handler(ctx: Context<RentCharacter>) -> Result<()> {
+    // The revenue goes to the renter from now on
+    sweep_owner_revenue(
+        &ctx.accounts.token_program,
+        &ctx.accounts.ai_character,
+        &ctx.accounts.owner_revenue_token_account,
+        Some(&ctx.accounts.lessor_compute_token_account),
+    )?;
+
+    let lease = &mut ctx.accounts.lease;
+
+    // Pay the rent up front to the lessor
+    if lease.rent_price > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.renter_compute_token_account.to_account_info(),
+                    to: ctx.accounts.lessor_compute_token_account.to_account_info(),
+                    authority: ctx.accounts.renter.to_account_info(),
+                },
+            ),
+            lease.rent_price,
+        )?;
+    }
+
+    lease.rent(ctx.accounts.renter.key(), Clock::get()?.unix_timestamp);
+
+    emit!(CharacterRented {
+        ai_character: lease.ai_character,
+        lessor: lease.lessor,
+        renter: lease.renter,
+        rent_price: lease.rent_price,
+        expires_at: lease.expires_at,
+    });
+
+    Ok(())
+}
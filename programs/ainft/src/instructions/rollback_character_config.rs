@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::CharacterConfigUpdated,
+    state::{config_fields, CharacterConfigHistory},
+};
+
+use super::UpdateCharacterConfigField;
+
+#[event]
+pub struct CharacterConfigRolledBack {
+    pub ai_character: Pubkey,
+    pub restored_version: u64,
+    pub new_version: u64,
+}
+
+pub fn rollback_character_config_handler(
+    ctx: Context<UpdateCharacterConfigField>,
+    version: u64,
+) -> Result<()> {
+    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
+    let mut config_history = CharacterConfigHistory::load_or_init(
+        &ctx.accounts.config_history,
+        ctx.accounts.ai_character.key(),
+        ctx.bumps.config_history,
+    )?;
+
+    ai_character.rollback_config(&mut config_history, version)?;
+
+    emit!(CharacterConfigUpdated {
+        ai_character: ctx.accounts.ai_character.key(),
+        owner: ctx.accounts.authority.key(),
+        version: ai_character.config_version,
+        previous_config_hash: ai_character.previous_config_hash,
+        updated_fields: config_fields::ALL_BUT_NAME,
+    });
+
+    emit!(CharacterConfigRolledBack {
+        ai_character: ctx.accounts.ai_character.key(),
+        restored_version: version,
+        new_version: ai_character.config_version,
+    });
+
+    Ok(())
+}
@@ -7,7 +7,8 @@ use crate::{
     error::AiNftError,
     events::MessageSent,
     state::{
-        message_constants::MESSAGE_AI_CHARACTER_SIZE, AiCharacterNFT, AiNft, MessageAiCharacter,
+        message_constants::MESSAGE_AI_CHARACTER_SIZE, pause_flags, AiCharacterNFT, AiNft,
+        MessageAiCharacter, ProtocolConfig,
     },
 };
 
@@ -31,13 +32,17 @@ pub struct SendMessage<'info> {
     pub ai_nft: Account<'info, AiNft>,
 
     // the ai character that receives the message
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
     pub ai_character: AccountLoader<'info, AiCharacterNFT>,
 
-    // The AI character's compute token account that will receive the payment
+    // The AI character's compute token account that will receive the payment,
+    // in the character's own compute mint if it has one
     #[account(
         mut,
-        constraint = compute_token_receiver.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = compute_token_receiver.mint == ai_character.load().unwrap().compute_mint_or(ai_nft.compute_mint) @ AiNftError::InvalidComputeMint,
         constraint = compute_token_receiver.owner == ai_character.key() @ AiNftError::InvalidTokenOwner,
     )]
     pub compute_token_receiver: Account<'info, TokenAccount>,
@@ -45,11 +50,19 @@ pub struct SendMessage<'info> {
     // The sender's compute token account that will pay for the message
     #[account(
         mut,
-        constraint = sender_compute_token.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+        constraint = sender_compute_token.mint == ai_character.load().unwrap().compute_mint_or(ai_nft.compute_mint) @ AiNftError::InvalidComputeMint,
         constraint = sender_compute_token.owner == sender.key() @ AiNftError::InvalidTokenOwner,
     )]
     pub sender_compute_token: Account<'info, TokenAccount>,
 
+    // protocol wide limits and pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::MESSAGING) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub sender: Signer<'info>,
 
@@ -62,12 +75,17 @@ pub fn send_message_handler(ctx: Context<SendMessage>, content: String) -> Resul
     let ai_nft = &mut ctx.accounts.ai_nft;
     let ai_character = &mut ctx.accounts.ai_character.load_mut().unwrap();
 
+    ctx.accounts.protocol_config.check_content(&content)?;
+
+    // The message fee plus the owner markup
+    let price = ai_character.message_price(ctx.accounts.protocol_config.message_fee)?;
+
     // Verify sender has enough compute tokens
-    if ctx.accounts.sender_compute_token.amount < 1 {
+    if ctx.accounts.sender_compute_token.amount < price {
         return err!(AiNftError::InsufficientCompute);
     }
 
-    // Transfer compute token from sender to AI character
+    // Transfer compute tokens from sender to AI character
     token::transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -77,7 +95,7 @@ pub fn send_message_handler(ctx: Context<SendMessage>, content: String) -> Resul
                 authority: ctx.accounts.sender.to_account_info(),
             },
         ),
-        1, // Transfer 1 compute token per message
+        price,
     )?;
 
     // Create the message
@@ -86,6 +104,7 @@ pub fn send_message_handler(ctx: Context<SendMessage>, content: String) -> Resul
         &ctx.accounts.ai_character.key(),
         &ctx.accounts.sender.key(),
         &content,
+        ai_character.owner_markup,
         ctx.bumps.message,
     );
 
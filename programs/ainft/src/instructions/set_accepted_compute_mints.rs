@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, ExecutionClient, ProtocolConfig},
+};
+
+#[event]
+pub struct AcceptedComputeMintsUpdated {
+    pub execution_client: Pubkey,
+    pub compute_mints: Vec<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptedComputeMints<'info> {
+    #[account(
+        mut,
+        constraint = execution_client.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub execution_client: Account<'info, ExecutionClient>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_accepted_compute_mints_handler(
+    ctx: Context<SetAcceptedComputeMints>,
+    compute_mints: Vec<Pubkey>,
+) -> Result<()> {
+    let execution_client = &mut ctx.accounts.execution_client;
+    execution_client.set_accepted_compute_mints(compute_mints.clone())?;
+
+    emit!(AcceptedComputeMintsUpdated {
+        execution_client: execution_client.key(),
+        compute_mints,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, AiCharacterNFT, AiNft, Lease, ProtocolConfig},
+};
+
+#[event]
+pub struct ForkSettingsUpdated {
+    pub ai_character: Pubkey,
+    pub owner: Pubkey,
+    pub fork_allowed: bool,
+    pub fork_fee: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetForkSettings<'info> {
+    // app ai nft account
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        mut,
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+        // holder only settings can't change while the character is rented out
+        constraint = Lease::load_active(&lease).is_none() @ AiNftError::LeaseActive,
+    )]
+    pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+}
+
+pub fn set_fork_settings_handler(
+    ctx: Context<SetForkSettings>,
+    fork_allowed: bool,
+    fork_fee: u64,
+) -> Result<()> {
+    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
+    ai_character.set_fork_settings(fork_allowed, fork_fee);
+
+    emit!(ForkSettingsUpdated {
+        ai_character: ctx.accounts.ai_character.key(),
+        owner: ctx.accounts.authority.key(),
+        fork_allowed,
+        fork_fee,
+    });
+
+    Ok(())
+}
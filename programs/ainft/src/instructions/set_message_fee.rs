@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AiNftError, state::ProtocolConfig};
+
+#[event]
+pub struct MessageFeeUpdated {
+    pub fee_manager: Pubkey,
+    pub message_fee: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetMessageFee<'info> {
+    #[account(
+        mut,
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = protocol_config.fee_manager == fee_manager.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub fee_manager: Signer<'info>,
+}
+
+pub fn set_message_fee_handler(ctx: Context<SetMessageFee>, message_fee: u64) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.set_message_fee(message_fee);
+
+    emit!(MessageFeeUpdated {
+        fee_manager: ctx.accounts.fee_manager.key(),
+        message_fee,
+    });
+
+    Ok(())
+}
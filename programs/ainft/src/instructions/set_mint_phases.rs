@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{AiNft, MintPhase},
+};
+
+#[event]
+pub struct MintPhasesUpdated {
+    pub ai_nft: Pubkey,
+    pub mint_phases: Vec<MintPhase>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintPhases<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_mint_phases_handler(
+    ctx: Context<SetMintPhases>,
+    mint_phases: Vec<MintPhase>,
+) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    ai_nft.set_mint_phases(mint_phases)?;
+
+    emit!(MintPhasesUpdated {
+        ai_nft: ai_nft.key(),
+        mint_phases: ai_nft.mint_phases.clone(),
+    });
+
+    Ok(())
+}
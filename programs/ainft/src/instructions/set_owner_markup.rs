@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, AiCharacterNFT, AiNft, Lease, ProtocolConfig},
+};
+
+#[event]
+pub struct OwnerMarkupUpdated {
+    pub ai_character: Pubkey,
+    pub owner: Pubkey,
+    pub owner_markup: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetOwnerMarkup<'info> {
+    // app ai nft account
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        mut,
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+        // holder only settings can't change while the character is rented out
+        constraint = Lease::load_active(&lease).is_none() @ AiNftError::LeaseActive,
+    )]
+    pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+
+    // the compute mint of the app ai nft
+    #[account(address = ai_nft.compute_mint @ AiNftError::InvalidComputeMint)]
+    pub compute_mint: Account<'info, Mint>,
+
+    // collects the owner markup paid with every message, until claimed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = ["owner_revenue".as_bytes(), ai_character.key().as_ref()],
+        bump,
+        token::mint = compute_mint,
+        token::authority = ai_character,
+    )]
+    pub owner_revenue_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_owner_markup_handler(ctx: Context<SetOwnerMarkup>, owner_markup: u64) -> Result<()> {
+    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
+    ai_character.set_owner_markup(owner_markup)?;
+
+    emit!(OwnerMarkupUpdated {
+        ai_character: ctx.accounts.ai_character.key(),
+        owner: ctx.accounts.authority.key(),
+        owner_markup,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{error::AiNftError, state::AiNft};
+
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub ai_nft: Pubkey,
+    pub protocol_fee_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    // the compute mint the responses are settled in
+    #[account(address = ai_nft.compute_mint @ AiNftError::InvalidComputeMint)]
+    pub compute_mint: Account<'info, Mint>,
+
+    // collects the protocol fee of every settled response
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = ["protocol_treasury".as_bytes(), ai_nft.key().as_ref()],
+        bump,
+        token::mint = compute_mint,
+        token::authority = ai_nft,
+    )]
+    pub protocol_treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_protocol_fee_handler(ctx: Context<SetProtocolFee>, protocol_fee_bps: u16) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    ai_nft.set_protocol_fee(protocol_fee_bps)?;
+
+    emit!(ProtocolFeeUpdated {
+        ai_nft: ai_nft.key(),
+        protocol_fee_bps,
+    });
+
+    Ok(())
+}
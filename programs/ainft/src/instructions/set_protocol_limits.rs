@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{ProtocolConfig, UpdateProtocolLimitsParams},
+};
+
+#[event]
+pub struct ProtocolLimitsUpdated {
+    pub max_content_length: u32,
+    pub max_response_length: u32,
+    pub max_gas: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolLimits<'info> {
+    #[account(
+        mut,
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == admin.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_protocol_limits_handler(
+    ctx: Context<SetProtocolLimits>,
+    params: UpdateProtocolLimitsParams,
+) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.set_limits(params)?;
+
+    emit!(ProtocolLimitsUpdated {
+        max_content_length: protocol_config.max_content_length,
+        max_response_length: protocol_config.max_response_length,
+        max_gas: protocol_config.max_gas,
+    });
+
+    Ok(())
+}
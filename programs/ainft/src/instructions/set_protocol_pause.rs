@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AiNftError, state::ProtocolConfig};
+
+#[event]
+pub struct ProtocolPauseUpdated {
+    pub pauser: Pubkey,
+    pub paused: u8,
+    pub withdraw_only: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    #[account(
+        mut,
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = protocol_config.pauser == pauser.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub pauser: Signer<'info>,
+}
+
+pub fn set_protocol_pause_handler(
+    ctx: Context<SetProtocolPause>,
+    paused: u8,
+    withdraw_only: bool,
+) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.set_pause(paused, withdraw_only)?;
+
+    emit!(ProtocolPauseUpdated {
+        pauser: ctx.accounts.pauser.key(),
+        paused,
+        withdraw_only,
+    });
+
+    Ok(())
+}
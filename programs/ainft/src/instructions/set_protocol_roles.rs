@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{ProtocolConfig, UpdateProtocolRolesParams},
+};
+
+#[event]
+pub struct ProtocolRolesUpdated {
+    pub admin: Pubkey,
+    pub fee_manager: Pubkey,
+    pub pauser: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolRoles<'info> {
+    #[account(
+        mut,
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == admin.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_protocol_roles_handler(
+    ctx: Context<SetProtocolRoles>,
+    params: UpdateProtocolRolesParams,
+) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.set_roles(params);
+
+    emit!(ProtocolRolesUpdated {
+        admin: protocol_config.admin,
+        fee_manager: protocol_config.fee_manager,
+        pauser: protocol_config.pauser,
+    });
+
+    Ok(())
+}
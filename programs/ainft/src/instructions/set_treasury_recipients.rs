@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{AiNft, TreasuryRecipient},
+};
+
+#[event]
+pub struct TreasuryRecipientsUpdated {
+    pub ai_nft: Pubkey,
+    pub recipients: Vec<TreasuryRecipient>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasuryRecipients<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_treasury_recipients_handler(
+    ctx: Context<SetTreasuryRecipients>,
+    recipients: Vec<TreasuryRecipient>,
+) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    ai_nft.set_treasury_recipients(recipients)?;
+
+    emit!(TreasuryRecipientsUpdated {
+        ai_nft: ai_nft.key(),
+        recipients: ai_nft.treasury_recipients.clone(),
+    });
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use anchor_spl::{
 
 use crate::{
     error::AiNftError,
-    state::{AiNft, ExecutionClient, Staker},
+    state::{pause_flags, AiNft, ExecutionClient, ProtocolConfig, Staker},
 };
 
 #[derive(Accounts)]
@@ -59,6 +59,14 @@ pub struct StakeCompute<'info> {
     )]
     pub staker_account: Account<'info, Staker>,
 
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::STAKING) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -3,7 +3,7 @@ use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 
 use crate::{
     error::AiNftError,
-    state::{ai_nft, AiNft, ExecutionClient, Staker},
+    state::{ai_nft, AiNft, ExecutionClient, ProtocolConfig, Staker},
 };
 
 #[derive(Accounts)]
@@ -52,6 +52,14 @@ pub struct UnstakeCompute<'info> {
     )]
     pub authority_liquid_staking_token_account: Account<'info, TokenAccount>,
 
+    // protocol wide pause flags, unstaking stays open in withdraw only mode
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = protocol_config.can_unstake() @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
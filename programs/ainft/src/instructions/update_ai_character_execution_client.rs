@@ -1,7 +1,7 @@
 use crate::state::AiNft;
 use crate::{
     error::AiNftError,
-    state::{AiCharacterNFT, ExecutionClient},
+    state::{pause_flags, AiCharacterNFT, ExecutionClient, Lease, ProtocolConfig},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
@@ -23,6 +23,14 @@ pub struct UpdateAiCharacterExecutionClient<'info> {
     )]
     pub ai_character: AccountLoader<'info, AiCharacterNFT>,
 
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -30,25 +38,31 @@ pub struct UpdateAiCharacterExecutionClient<'info> {
     #[account(
         mut,
         constraint = ai_character_mint.key() == ai_character.load().unwrap().character_nft_mint @ AiNftError::InvalidAgentNftMint,
-        constraint = ai_character_mint.mint_authority.is_some() && ai_character_mint.mint_authority.unwrap() == ai_nft.key() @ AiNftError::InvalidMintAuthority,
+        // the mint authority moves to the master edition, so check the collection through the ai character
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
     )]
     pub ai_character_mint: Account<'info, Mint>,
 
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
     // token account of the agent nft
     #[account(
         mut,
         // mint of the token account should be the agent nft mint
         constraint = ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
-        // owner of the token account should be the authority
-        constraint = ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
-        constraint = ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
-    
+        // the authority holds the agent nft, or rents it through an active lease
+        constraint = Lease::is_character_authority(&lease, &ai_character_token_account, &authority.key()) @ AiNftError::InvalidOwner,
     )]
     pub ai_character_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
         constraint = execution_client.active @ AiNftError::ExecutionClientNotActive,
+        constraint = execution_client.ai_nft == ai_nft.key() @ AiNftError::InvalidAiNft,
+        // the new client has to settle responses in the compute mint of the character
+        constraint = execution_client.accepts_compute_mint(&ai_character.load().unwrap().compute_mint_or(ai_nft.compute_mint)) @ AiNftError::UnsupportedComputeMint,
     )]
     pub execution_client: Account<'info, ExecutionClient>,
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{AiNft, UpdateAiNftParams},
+};
+
+#[event]
+pub struct CollectionUpdated {
+    pub ai_nft: Pubkey,
+    pub mint_price: u64,
+    pub max_supply: u64,
+    pub default_execution_client: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAppAiNft<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn update_app_ainft_handler(
+    ctx: Context<UpdateAppAiNft>,
+    params: UpdateAiNftParams,
+) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    ai_nft.update(params)?;
+
+    emit!(CollectionUpdated {
+        ai_nft: ai_nft.key(),
+        mint_price: ai_nft.mint_price,
+        max_supply: ai_nft.max_supply,
+        default_execution_client: ai_nft.default_execution_client,
+    });
+
+    Ok(())
+}
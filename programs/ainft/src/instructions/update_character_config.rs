@@ -1,9 +1,15 @@
+use std::mem::size_of;
+
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
 
 use crate::{
     error::AiNftError,
-    state::{AiCharacterNFT, AiNft, CharacterConfig, CharacterConfigInput},
+    events::CharacterConfigUpdated,
+    state::{
+        config_fields, pause_flags, AiCharacterNFT, AiNft, CharacterConfig, CharacterConfigHistory,
+        CharacterConfigInput, Lease, ProtocolConfig,
+    },
 };
 
 #[derive(Accounts)]
@@ -19,6 +25,14 @@ pub struct UpdateAiCharacter<'info> {
     )]
     pub ai_character: AccountLoader<'info, AiCharacterNFT>,
 
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -26,32 +40,59 @@ pub struct UpdateAiCharacter<'info> {
     #[account(
         mut,
         constraint = ai_character_mint.key() == ai_character.load().unwrap().character_nft_mint @ AiNftError::InvalidAgentNftMint,
-        constraint = ai_character_mint.mint_authority.is_some() && ai_character_mint.mint_authority.unwrap() == ai_nft.key() @ AiNftError::InvalidMintAuthority,
+        // the mint authority moves to the master edition, so check the collection through the ai character
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
     )]
     pub ai_character_mint: Account<'info, Mint>,
 
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
     // token account of the agent nft
     #[account(
         mut,
         // mint of the token account should be the agent nft mint
         constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
-        // owner of the token account should be the authority
-        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
-       // the token accounts should contain the agent nft
-       constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+        // the authority holds the agent nft, or rents it through an active lease
+        constraint = Lease::is_character_authority(&lease, &authority_ai_character_token_account, &authority.key()) @ AiNftError::InvalidOwner,
     )]
     pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+
+    // previous configs of the ai character, created on the first update
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<CharacterConfigHistory>(),
+        seeds = ["config_history".as_bytes(), ai_character.key().as_ref()],
+        bump,
+    )]
+    pub config_history: AccountLoader<'info, CharacterConfigHistory>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn update_character_config_handler(
     ctx: Context<UpdateAiCharacter>,
     new_config: CharacterConfigInput,
 ) -> Result<()> {
-    ctx.accounts
-        .ai_character
-        .load_mut()
-        .unwrap()
-        .update_character_config(new_config)?;
+    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
+    let mut config_history = CharacterConfigHistory::load_or_init(
+        &ctx.accounts.config_history,
+        ctx.accounts.ai_character.key(),
+        ctx.bumps.config_history,
+    )?;
+
+    ai_character.checkpoint_config(&mut config_history)?;
+    ai_character.update_character_config(new_config)?;
+
+    emit!(CharacterConfigUpdated {
+        ai_character: ctx.accounts.ai_character.key(),
+        owner: ctx.accounts.authority.key(),
+        version: ai_character.config_version,
+        previous_config_hash: ai_character.previous_config_hash,
+        updated_fields: config_fields::ALL_BUT_NAME,
+    });
 
     Ok(())
 }
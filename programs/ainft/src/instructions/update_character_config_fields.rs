@@ -1,9 +1,14 @@
 use crate::{
     error::AiNftError,
-    state::{AiCharacterNFT, AiNft, StyleConfigInput},
+    events::CharacterConfigUpdated,
+    state::{
+        config_fields, pause_flags, AiCharacterNFT, AiNft, CharacterConfigHistory, ConfigFieldValue,
+        Lease, ModelSettingsInput, ProtocolConfig, StyleConfigInput,
+    },
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
+use std::mem::size_of;
 
 #[derive(Accounts)]
 pub struct UpdateCharacterConfigField<'info> {
@@ -18,6 +23,14 @@ pub struct UpdateCharacterConfigField<'info> {
      )]
     pub ai_character: AccountLoader<'info, AiCharacterNFT>,
 
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -25,123 +38,234 @@ pub struct UpdateCharacterConfigField<'info> {
     #[account(
          mut,
          constraint = ai_character_mint.key() == ai_character.load().unwrap().character_nft_mint @ AiNftError::InvalidAgentNftMint,
-         constraint = ai_character_mint.mint_authority.is_some() && ai_character_mint.mint_authority.unwrap() == ai_nft.key() @ AiNftError::InvalidMintAuthority,
+         // the mint authority moves to the master edition, so check the collection through the ai character
+         constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
      )]
     pub ai_character_mint: Account<'info, Mint>,
 
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
     // token account of the agent nft
     #[account(
          mut,
          // mint of the token account should be the agent nft mint
          constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
-         // owner of the token account should be the authority
-         constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
-        // the token accounts should contain the agent nft
-        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
-     )]
+         // the authority holds the agent nft, or rents it through an active lease
+         constraint = Lease::is_character_authority(&lease, &authority_ai_character_token_account, &authority.key()) @ AiNftError::InvalidOwner,
+    )]
     pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+
+    // previous configs of the ai character, created on the first update
+    #[account(
+         init_if_needed,
+         payer = authority,
+         space = 8 + size_of::<CharacterConfigHistory>(),
+         seeds = ["config_history".as_bytes(), ai_character.key().as_ref()],
+         bump,
+     )]
+    pub config_history: AccountLoader<'info, CharacterConfigHistory>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn update_character_name_handler(
-    ctx: Context<UpdateCharacterConfigField>,
-    name: String,
-) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_name(name)
+impl<'info> UpdateCharacterConfigField<'info> {
+    /// Checkpoints the current config, applies `update` and emits the update event
+    pub fn apply_config_update(
+        &self,
+        config_history_bump: u8,
+        updated_fields: u32,
+        update: impl FnOnce(&mut AiCharacterNFT) -> Result<()>,
+    ) -> Result<()> {
+        let mut ai_character = self.ai_character.load_mut()?;
+        let mut config_history = CharacterConfigHistory::load_or_init(
+            &self.config_history,
+            self.ai_character.key(),
+            config_history_bump,
+        )?;
+
+        ai_character.checkpoint_config(&mut config_history)?;
+        update(&mut ai_character)?;
+
+        emit!(CharacterConfigUpdated {
+            ai_character: self.ai_character.key(),
+            owner: self.authority.key(),
+            version: ai_character.config_version,
+            previous_config_hash: ai_character.previous_config_hash,
+            updated_fields,
+        });
+
+        Ok(())
+    }
 }
 
 pub fn update_character_clients_handler(
     ctx: Context<UpdateCharacterConfigField>,
     clients: Vec<String>,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_clients(clients)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::CLIENTS,
+        |ai_character| ai_character.update_config_clients(clients),
+    )
 }
 
 pub fn update_character_model_provider_handler(
     ctx: Context<UpdateCharacterConfigField>,
     provider: String,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_model_provider(provider)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::MODEL_PROVIDER,
+        |ai_character| ai_character.update_config_model_provider(provider),
+    )
 }
 
 pub fn update_character_voice_settings_handler(
     ctx: Context<UpdateCharacterConfigField>,
     model: [u8; 32],
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_voice_settings(model)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::VOICE_SETTINGS,
+        |ai_character| ai_character.update_config_voice_settings(model),
+    )
+}
+
+pub fn update_character_model_settings_handler(
+    ctx: Context<UpdateCharacterConfigField>,
+    model_settings: ModelSettingsInput,
+) -> Result<()> {
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::MODEL_SETTINGS,
+        |ai_character| ai_character.update_config_model_settings(model_settings),
+    )
+}
+
+pub fn update_character_system_prompt_handler(
+    ctx: Context<UpdateCharacterConfigField>,
+    system_prompt_hash: [u8; 32],
+    system_prompt_uri: String,
+) -> Result<()> {
+    // the system prompt is part of the model settings
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::MODEL_SETTINGS,
+        |ai_character| {
+            ai_character.update_config_system_prompt(system_prompt_hash, system_prompt_uri)
+        },
+    )
 }
 
 pub fn update_character_bio_handler(
     ctx: Context<UpdateCharacterConfigField>,
     bio: Vec<String>,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_bio(bio)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::BIO,
+        |ai_character| ai_character.update_config_bio(bio),
+    )
 }
 
 pub fn update_character_lore_handler(
     ctx: Context<UpdateCharacterConfigField>,
     lore: Vec<String>,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_lore(lore)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::LORE,
+        |ai_character| ai_character.update_config_lore(lore),
+    )
 }
 
 pub fn update_character_knowledge_handler(
     ctx: Context<UpdateCharacterConfigField>,
     knowledge: Vec<String>,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_knowledge(knowledge)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::KNOWLEDGE,
+        |ai_character| ai_character.update_config_knowledge(knowledge),
+    )
 }
 
 pub fn update_character_topics_handler(
     ctx: Context<UpdateCharacterConfigField>,
     topics: Vec<String>,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_topics(topics)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::TOPICS,
+        |ai_character| ai_character.update_config_topics(topics),
+    )
 }
 
 pub fn update_character_style_handler(
     ctx: Context<UpdateCharacterConfigField>,
     style: StyleConfigInput,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_style(style)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::STYLE,
+        |ai_character| ai_character.update_config_style(style),
+    )
 }
 
 pub fn update_character_adjectives_handler(
     ctx: Context<UpdateCharacterConfigField>,
     adjectives: Vec<String>,
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_adjectives(adjectives)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::ADJECTIVES,
+        |ai_character| ai_character.update_config_adjectives(adjectives),
+    )
 }
 
 pub fn update_character_style_all_handler(
     ctx: Context<UpdateCharacterConfigField>,
     style_all: [[u8; 32]; 10],
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_style_all(style_all)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::STYLE_ALL,
+        |ai_character| ai_character.update_config_style_all(style_all),
+    )
 }
 
 pub fn update_character_style_chat_handler(
     ctx: Context<UpdateCharacterConfigField>,
     style_chat: [[u8; 32]; 10],
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_style_chat(style_chat)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::STYLE_CHAT,
+        |ai_character| ai_character.update_config_style_chat(style_chat),
+    )
 }
 
 pub fn update_character_style_post_handler(
     ctx: Context<UpdateCharacterConfigField>,
     style_post: [[u8; 32]; 10],
 ) -> Result<()> {
-    let mut ai_character = ctx.accounts.ai_character.load_mut()?;
-    ai_character.update_config_style_post(style_post)
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        config_fields::STYLE_POST,
+        |ai_character| ai_character.update_config_style_post(style_post),
+    )
+}
+
+pub fn update_character_fields_handler(
+    ctx: Context<UpdateCharacterConfigField>,
+    field_mask: u32,
+    values: Vec<ConfigFieldValue>,
+) -> Result<()> {
+    ctx.accounts.apply_config_update(
+        ctx.bumps.config_history,
+        field_mask,
+        |ai_character| ai_character.update_config_fields(field_mask, values),
+    )
 }
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{
+    self,
+    mpl_token_metadata::{types::DataV2, MAX_URI_LENGTH},
+    Metadata, MetadataAccount, UpdateMetadataAccountsV2,
+};
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, AiCharacterNFT, AiNft, Lease, ProtocolConfig},
+};
+
+#[event]
+pub struct CharacterMetadataUpdated {
+    pub ai_character: Pubkey,
+    pub owner: Pubkey,
+    pub uri: String,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCharacterMetadata<'info> {
+    // app ai nft account, update authority of the character metadata
+    pub ai_nft: Account<'info, AiNft>,
+
+    #[account(
+        // make sure the ai character is associated with the agent nft mint
+        constraint = ai_character.load().unwrap().character_nft_mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        constraint = ai_character.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
+    pub ai_character: AccountLoader<'info, AiCharacterNFT>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub authority: Signer<'info>,
+
+    // the mint of the agent nft
+    pub ai_character_mint: Account<'info, Mint>,
+
+    /// CHECK: Lease PDA of the ai character, it may not exist
+    #[account(seeds = ["lease".as_bytes(), ai_character.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    // token account of the agent nft
+    #[account(
+        // mint of the token account should be the agent nft mint
+        constraint = authority_ai_character_token_account.mint == ai_character_mint.key() @ AiNftError::InvalidAgentNftMint,
+        // owner of the token account should be the authority
+        constraint = authority_ai_character_token_account.owner == authority.key() @ AiNftError::InvalidOwner,
+        // the token accounts should contain the agent nft
+        constraint = authority_ai_character_token_account.amount == 1 @ AiNftError::InvalidAgentNftTokenAccount,
+        // holder only settings can't change while the character is rented out
+        constraint = Lease::load_active(&lease).is_none() @ AiNftError::LeaseActive,
+    )]
+    pub authority_ai_character_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            metadata_program.key().as_ref(),
+            ai_character_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub ai_character_metadata: Box<Account<'info, MetadataAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+/// Updates the name and/or uri of a character's metadata, keeping the rest of
+/// its data. The ai nft signs as the update authority.
+pub fn update_character_metadata_data<'info>(
+    metadata_program: &Program<'info, Metadata>,
+    ai_character_metadata: &Account<'info, MetadataAccount>,
+    ai_nft: &Account<'info, AiNft>,
+    name: Option<&str>,
+    uri: Option<&str>,
+) -> Result<()> {
+    // The metadata program pads the stored strings
+    let cpi_context = CpiContext::new(
+        metadata_program.to_account_info(),
+        UpdateMetadataAccountsV2 {
+            metadata: ai_character_metadata.to_account_info(),
+            update_authority: ai_nft.to_account_info(),
+        },
+    );
+    metadata::update_metadata_accounts_v2(
+        cpi_context.with_signer(&[&ai_nft.as_seeds()]),
+        None,
+        Some(DataV2 {
+            name: name
+                .unwrap_or(ai_character_metadata.name.trim_end_matches('\0'))
+                .to_string(),
+            symbol: ai_character_metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: uri
+                .unwrap_or(ai_character_metadata.uri.trim_end_matches('\0'))
+                .to_string(),
+            seller_fee_basis_points: ai_character_metadata.seller_fee_basis_points,
+            creators: ai_character_metadata.creators.clone(),
+            collection: ai_character_metadata.collection.clone(),
+            uses: ai_character_metadata.uses.clone(),
+        }),
+        None,
+        None,
+    )
+}
+
+pub fn update_character_metadata_handler(
+    ctx: Context<UpdateCharacterMetadata>,
+    uri: String,
+) -> Result<()> {
+    require!(
+        !uri.is_empty() && uri.len() <= MAX_URI_LENGTH,
+        AiNftError::InvalidMetadataUri
+    );
+
+    update_character_metadata_data(
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.ai_character_metadata,
+        &ctx.accounts.ai_nft,
+        None,
+        Some(&uri),
+    )?;
+
+    emit!(CharacterMetadataUpdated {
+        ai_character: ctx.accounts.ai_character.key(),
+        owner: ctx.accounts.authority.key(),
+        uri,
+    });
+
+    Ok(())
+}
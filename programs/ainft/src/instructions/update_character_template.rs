@@ -0,0 +1,70 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{pause_flags, AiNft, CharacterConfigInput, CharacterTemplate, ProtocolConfig},
+};
+
+#[event]
+pub struct CharacterTemplateUpdated {
+    pub ai_nft: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCharacterTemplate<'info> {
+    #[account(
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    // the config that new ai characters of the collection start from
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<CharacterTemplate>(),
+        seeds = ["character_template".as_bytes(), ai_nft.key().as_ref()],
+        bump,
+    )]
+    pub character_template: AccountLoader<'info, CharacterTemplate>,
+
+    // protocol wide pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_character_template_handler(
+    ctx: Context<UpdateCharacterTemplate>,
+    character_config: CharacterConfigInput,
+) -> Result<()> {
+    let mut character_template = CharacterTemplate::load_or_init(
+        &ctx.accounts.character_template,
+        ctx.accounts.ai_nft.key(),
+        ctx.bumps.character_template,
+    )?;
+    character_template.update_character_config(character_config)?;
+
+    emit!(CharacterTemplateUpdated {
+        ai_nft: ctx.accounts.ai_nft.key(),
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
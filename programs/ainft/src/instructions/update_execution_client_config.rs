@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{events::ExecutionClientConfigUpdated, state::ExecutionClient};
+use crate::{
+    error::AiNftError,
+    events::ExecutionClientConfigUpdated,
+    state::{pause_flags, ExecutionClient, ProtocolConfig},
+};
 
 #[derive(Accounts)]
 pub struct UpdateExecutionClientConfig<'info> {
@@ -10,6 +14,14 @@ pub struct UpdateExecutionClientConfig<'info> {
     )]
     pub execution_client: Account<'info, ExecutionClient>,
 
+    // protocol wide limits and pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::CONFIG_UPDATES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     pub authority: Signer<'info>,
 }
 
@@ -20,7 +32,7 @@ pub fn update_execution_client_config_handler(
     let execution_client = &mut ctx.accounts.execution_client;
 
     // Update the fee
-    execution_client.update_gas(new_gas)?;
+    execution_client.update_gas(new_gas, ctx.accounts.protocol_config.max_gas)?;
 
     emit!(ExecutionClientConfigUpdated {
         client: execution_client.key(),
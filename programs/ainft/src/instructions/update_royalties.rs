@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AiNftError,
+    state::{AiNft, RoyaltyConfig},
+};
+
+#[event]
+pub struct RoyaltiesUpdated {
+    pub ai_nft: Pubkey,
+    pub seller_fee_basis_points: u16,
+    pub creator_count: u8,
+    pub minter_share: u8,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRoyalties<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn update_royalties_handler(
+    ctx: Context<UpdateRoyalties>,
+    royalties: RoyaltyConfig,
+) -> Result<()> {
+    let ai_nft = &mut ctx.accounts.ai_nft;
+    ai_nft.set_royalties(royalties)?;
+
+    emit!(RoyaltiesUpdated {
+        ai_nft: ai_nft.key(),
+        seller_fee_basis_points: ai_nft.royalties.seller_fee_basis_points,
+        creator_count: ai_nft.royalties.creators.len() as u8,
+        minter_share: ai_nft.royalties.minter_share,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{error::AiNftError, state::AiNft};
+
+#[event]
+pub struct MintProceedsWithdrawn {
+    pub ai_nft: Pubkey,
+    pub recipient: Pubkey,
+    pub payment_mint: Pubkey, // default for SOL
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawMintProceeds<'info> {
+    #[account(
+        mut,
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    // collection treasury, withdraws the proceeds paid in the payment mint if set
+    #[account(
+        mut,
+        token::authority = ai_nft,
+        constraint = treasury_token_account.mint == ai_nft.payment_mint @ AiNftError::InvalidPaymentMint,
+    )]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining accounts: the treasury recipients in the configured order, or
+    // their token accounts when withdrawing tokens. Lamports withdrawn without
+    // configured recipients go to the authority.
+}
+
+/// Withdraws `amount` of mint proceeds (everything available if `None`) and
+/// splits them between the treasury recipients.
+pub fn withdraw_mint_proceeds_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawMintProceeds<'info>>,
+    amount: Option<u64>,
+) -> Result<()> {
+    match &ctx.accounts.treasury_token_account {
+        Some(treasury_token_account) => {
+            withdraw_tokens(&ctx, treasury_token_account, amount)
+        }
+        None => withdraw_lamports(&ctx, amount),
+    }
+}
+
+fn withdraw_lamports<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, WithdrawMintProceeds<'info>>,
+    amount: Option<u64>,
+) -> Result<()> {
+    let ai_nft = &ctx.accounts.ai_nft;
+    let ai_nft_info = ai_nft.to_account_info();
+
+    // Keep the collection account rent exempt
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(ai_nft_info.data_len());
+    let available = ai_nft_info
+        .lamports()
+        .saturating_sub(rent_exempt_minimum);
+    let amount = amount.unwrap_or(available);
+    require!(amount > 0, AiNftError::InsufficientProceeds);
+    require!(amount <= available, AiNftError::InsufficientProceeds);
+
+    let payouts = ai_nft.split_proceeds(amount);
+    let recipients: Vec<AccountInfo<'info>> = if ai_nft.treasury_recipients.is_empty() {
+        vec![ctx.accounts.authority.to_account_info()]
+    } else {
+        ctx.remaining_accounts.to_vec()
+    };
+    require!(
+        recipients.len() == payouts.len(),
+        AiNftError::InvalidTreasuryRecipients
+    );
+
+    for ((address, share), recipient) in payouts.into_iter().zip(recipients.iter()) {
+        require_keys_eq!(
+            recipient.key(),
+            address,
+            AiNftError::InvalidTreasuryRecipients
+        );
+        if share == 0 {
+            continue;
+        }
+        ai_nft_info.sub_lamports(share)?;
+        recipient.add_lamports(share)?;
+
+        emit!(MintProceedsWithdrawn {
+            ai_nft: ai_nft.key(),
+            recipient: address,
+            payment_mint: Pubkey::default(),
+            amount: share,
+        });
+    }
+
+    Ok(())
+}
+
+fn withdraw_tokens<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, WithdrawMintProceeds<'info>>,
+    treasury_token_account: &Account<'info, TokenAccount>,
+    amount: Option<u64>,
+) -> Result<()> {
+    let ai_nft = &ctx.accounts.ai_nft;
+    let amount = amount.unwrap_or(treasury_token_account.amount);
+    require!(amount > 0, AiNftError::InsufficientProceeds);
+    require!(
+        amount <= treasury_token_account.amount,
+        AiNftError::InsufficientProceeds
+    );
+
+    let payouts = ai_nft.split_proceeds(amount);
+    require!(
+        ctx.remaining_accounts.len() == payouts.len(),
+        AiNftError::InvalidTreasuryRecipients
+    );
+
+    for ((address, share), recipient) in payouts.into_iter().zip(ctx.remaining_accounts.iter()) {
+        let recipient_token_account = Account::<TokenAccount>::try_from(recipient)?;
+        require_keys_eq!(
+            recipient_token_account.owner,
+            address,
+            AiNftError::InvalidTreasuryRecipients
+        );
+        require_keys_eq!(
+            recipient_token_account.mint,
+            ai_nft.payment_mint,
+            AiNftError::InvalidPaymentMint
+        );
+        if share == 0 {
+            continue;
+        }
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: treasury_token_account.to_account_info(),
+                to: recipient.clone(),
+                authority: ai_nft.to_account_info(),
+            },
+        );
+        token::transfer(cpi_context.with_signer(&[&ai_nft.as_seeds()]), share)?;
+
+        emit!(MintProceedsWithdrawn {
+            ai_nft: ai_nft.key(),
+            recipient: address,
+            payment_mint: ai_nft.payment_mint,
+            amount: share,
+        });
+    }
+
+    Ok(())
+}
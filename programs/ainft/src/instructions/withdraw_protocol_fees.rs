@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{error::AiNftError, state::AiNft};
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub ai_nft: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        seeds = [
+            "app_ainft".as_bytes(),
+            ai_nft.creator.as_ref(),
+            ai_nft.collection_id.to_le_bytes().as_ref(),
+        ],
+        bump = ai_nft.bump[0],
+        constraint = ai_nft.authority == authority.key() @ AiNftError::InvalidAuthority,
+    )]
+    pub ai_nft: Box<Account<'info, AiNft>>,
+
+    #[account(
+        mut,
+        seeds = ["protocol_treasury".as_bytes(), ai_nft.key().as_ref()],
+        bump,
+    )]
+    pub protocol_treasury_token_account: Account<'info, TokenAccount>,
+
+    // compute token account receiving the fees
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == ai_nft.compute_mint @ AiNftError::InvalidComputeMint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_protocol_fees_handler(
+    ctx: Context<WithdrawProtocolFees>,
+    amount: Option<u64>,
+) -> Result<()> {
+    let available = ctx.accounts.protocol_treasury_token_account.amount;
+    let amount = amount.unwrap_or(available);
+    require!(
+        amount > 0 && amount <= available,
+        AiNftError::InsufficientProceeds
+    );
+
+    let ai_nft = &ctx.accounts.ai_nft;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.protocol_treasury_token_account.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ai_nft.to_account_info(),
+            },
+        )
+        .with_signer(&[&ai_nft.as_seeds()]),
+        amount,
+    )?;
+
+    emit!(ProtocolFeesWithdrawn {
+        ai_nft: ai_nft.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -2,13 +2,21 @@ use crate::actions::{Action, ActionParams};
 use crate::error::AiNftError;
 use crate::events::ResponseWritten;
 use crate::state::{
-    ai_nft, execution_client, AiCharacterNFT, AiNft, ExecutionClient, MessageAiCharacter,
+    ai_nft, execution_client, pause_flags, AiCharacterNFT, AiNft, ExecutionClient,
+    MessageAiCharacter, ProtocolConfig,
 };
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::entrypoint::ProgramResult;
 use anchor_lang::solana_program::stake;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
+#[event]
+pub struct ProtocolFeeCollected {
+    pub ai_nft: Pubkey,
+    pub message: Pubkey,
+    pub amount: u64,
+}
+
 #[derive(Accounts)]
 pub struct WriteResponse<'info> {
 
@@ -27,7 +35,10 @@ pub struct WriteResponse<'info> {
 
 
     // The ai character nft account that the message is for
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ai_character_nft.load().unwrap().app_ai_nft_mint == ai_nft.master_mint @ AiNftError::InvalidAiNft,
+    )]
     pub ai_character_nft: AccountLoader<'info, AiCharacterNFT>,
 
     // The ai character compute token account. When the
@@ -64,6 +75,41 @@ pub struct WriteResponse<'info> {
     )]
     pub execution_client_compute_token_address: Account<'info, TokenAccount>,
 
+    // The execution client authority token account in the compute mint of the
+    // character. Required if the character has its own compute mint
+    #[account(
+        mut,
+        constraint = execution_client_character_token_account.owner == execution_client.authority @ AiNftError::InvalidTokenOwner,
+        constraint = execution_client_character_token_account.mint == ai_character_nft.load().unwrap().character_compute_mint @ AiNftError::InvalidComputeMint,
+    )]
+    pub execution_client_character_token_account: Option<Account<'info, TokenAccount>>,
+
+    // The owner revenue account of the ai character, receives the owner markup
+    // paid with the message. Required if the message carries a markup
+    #[account(
+        mut,
+        seeds = ["owner_revenue".as_bytes(), ai_character_nft.key().as_ref()],
+        bump,
+    )]
+    pub owner_revenue_token_account: Option<Account<'info, TokenAccount>>,
+
+    // The protocol treasury of the app ai nft, receives the protocol fee.
+    // Required if the app ai nft has a protocol fee
+    #[account(
+        mut,
+        seeds = ["protocol_treasury".as_bytes(), ai_nft.key().as_ref()],
+        bump,
+    )]
+    pub protocol_treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+
+    // protocol wide limits and pause flags
+    #[account(
+        seeds = ["protocol_config".as_bytes()],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(pause_flags::RESPONSES) @ AiNftError::ProtocolPaused,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
     // The signer of the transaction which is the execution client authority
     #[account(mut)]
@@ -96,10 +142,15 @@ pub struct ResponseWithActions {
 // it also transfers compute tokens to the execution client2
 pub fn write_response_handler(ctx: Context<WriteResponse>, response: ResponseWithActions) -> Result<()> {
     // Load the account once at the beginning
-    let (character_nft_mint, ai_character_bump) = {
+    let (character_nft_mint, ai_character_bump, has_compute_mint, character_compute_mint) = {
         let mut ai_character_nft = ctx.accounts.ai_character_nft.load_mut()?;
         ai_character_nft.total_processed += 1;
-        (ai_character_nft.character_nft_mint, ai_character_nft.bump)
+        (
+            ai_character_nft.character_nft_mint,
+            ai_character_nft.bump,
+            ai_character_nft.has_compute_mint(),
+            ai_character_nft.character_compute_mint,
+        )
     };
     // Get the seeds once
     let ai_character_seeds = &[
@@ -108,10 +159,58 @@ pub fn write_response_handler(ctx: Context<WriteResponse>, response: ResponseWit
         &ai_character_bump,
     ];
     
-    // Calculate fee split
-    let fee_split = ctx.accounts.execution_client.calculate_fee_split();
+    // Calculate fee split. Characters with their own compute mint pay the whole
+    // gas to the client, stakers and the protocol treasury hold the collection mint
+    let (protocol_fee, staker_fee, client_fee) = if has_compute_mint {
+        require!(
+            ctx.accounts.execution_client.accepts_compute_mint(&character_compute_mint),
+            AiNftError::UnsupportedComputeMint
+        );
+        (0, 0, ctx.accounts.execution_client.gas)
+    } else {
+        ctx.accounts
+            .execution_client
+            .calculate_fee_split_with_protocol_fee(ctx.accounts.ai_nft.protocol_fee_bps)
+    };
+    let fee_split = (staker_fee, client_fee);
+    let client_token_account = if has_compute_mint {
+        ctx.accounts
+            .execution_client_character_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingComputeTokenAccount)?
+            .to_account_info()
+    } else {
+        ctx.accounts.execution_client_compute_token_address.to_account_info()
+    };
 
     msg!("fee_split: {:?}", fee_split);
+    // Transfer protocol fee
+    if protocol_fee > 0 {
+        let protocol_treasury_token_account = ctx
+            .accounts
+            .protocol_treasury_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingProtocolTreasuryAccount)?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.ai_character_compute_token_account.to_account_info(),
+                    to: protocol_treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.ai_character_nft.to_account_info(),
+                },
+                &[ai_character_seeds],
+            ),
+            protocol_fee,
+        )?;
+
+        emit!(ProtocolFeeCollected {
+            ai_nft: ctx.accounts.ai_nft.key(),
+            message: ctx.accounts.message.key(),
+            amount: protocol_fee,
+        });
+    }
+
     // Transfer staker portion
     if fee_split.0 > 0 {
         token::transfer(
@@ -135,7 +234,7 @@ pub fn write_response_handler(ctx: Context<WriteResponse>, response: ResponseWit
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
                     from: ctx.accounts.ai_character_compute_token_account.to_account_info(),
-                    to: ctx.accounts.execution_client_compute_token_address.to_account_info(),
+                    to: client_token_account,
                     authority: ctx.accounts.ai_character_nft.to_account_info(),
                 },
                 &[ai_character_seeds],
@@ -144,10 +243,35 @@ pub fn write_response_handler(ctx: Context<WriteResponse>, response: ResponseWit
         )?;
     }
 
+    // Transfer owner markup
+    let owner_markup = ctx.accounts.message.owner_markup;
+    if owner_markup > 0 {
+        let owner_revenue_token_account = ctx
+            .accounts
+            .owner_revenue_token_account
+            .as_ref()
+            .ok_or(AiNftError::MissingOwnerRevenueAccount)?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.ai_character_compute_token_account.to_account_info(),
+                    to: owner_revenue_token_account.to_account_info(),
+                    authority: ctx.accounts.ai_character_nft.to_account_info(),
+                },
+                &[ai_character_seeds],
+            ),
+            owner_markup,
+        )?;
+    }
+
     // Update state
     ctx.accounts.execution_client.add_fees(fee_split.0);
     ctx.accounts.execution_client.total_processed += 1;
-    ctx.accounts.message.answer(&response.content);
+    ctx.accounts.message.answer(
+        &response.content,
+        ctx.accounts.protocol_config.max_response_length as usize,
+    );
 
     Ok(())
 }
@@ -7,6 +7,8 @@ pub mod instructions;
 mod state;
 use instructions::*;
 use state::CharacterConfigInput;
+use state::ConfigFieldValue;
+use state::ModelSettingsInput;
 use state::StyleConfigInput;
 
 declare_id!("14M8GDtWobqndjTrJ4sDZJ2CY74TXyGWGJzJoAE4TNYh");
@@ -25,11 +27,17 @@ pub mod ainft {
     /// A collection serves as a container for AI NFTs with shared properties like
     /// maximum supply, mint price, and default execution client.
     ///
+    /// The collection PDA is seeded by the creator and a collection id, so one
+    /// deployment can host several collections.
+    ///
     /// # Arguments
+    /// * `collection_id` - Id distinguishing the collections of one creator
     /// * `name` - The name of the collection
     /// * `max_supply` - Maximum number of NFTs that can be minted in this collection
-    /// * `mint_price` - Price in lamports to mint each NFT
+    /// * `mint_price` - Price in lamports to mint each NFT, or in tokens of the payment mint
+    /// * `payment_mint` - Optional token the mint price is paid in instead of SOL
     /// * `default_execution_client` - Default execution client for NFTs in this collection
+    /// * `royalties` - Royalty basis points and creator split applied to every minted NFT
     pub fn create_app_ainft(
         ctx: Context<CreateAppAiNft>,
         create_ai_nft_params: state::CreateAiNftParams,
@@ -37,6 +45,105 @@ pub mod ainft {
         instructions::create_app_ainft_handler(ctx, create_ai_nft_params)
     }
 
+    /// Creates the protocol config
+    ///
+    /// The protocol config holds the protocol wide limits and the admin,
+    /// fee manager and pauser roles. Only the program upgrade authority can
+    /// create it, and it starts with all roles.
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        instructions::initialize_protocol_config_handler(ctx)
+    }
+
+    /// Updates the roles of the protocol config
+    ///
+    /// Only the admin can update the roles. Roles that are not set are kept.
+    ///
+    /// # Arguments
+    /// * `params` - New admin, fee manager and pauser
+    pub fn set_protocol_roles(
+        ctx: Context<SetProtocolRoles>,
+        params: state::UpdateProtocolRolesParams,
+    ) -> Result<()> {
+        instructions::set_protocol_roles_handler(ctx, params)
+    }
+
+    /// Updates the limits of the protocol config
+    ///
+    /// Only the admin can update the limits. Message length limits can't exceed
+    /// the size message accounts are allocated with.
+    ///
+    /// # Arguments
+    /// * `params` - New max content length, max response length and gas cap
+    pub fn set_protocol_limits(
+        ctx: Context<SetProtocolLimits>,
+        params: state::UpdateProtocolLimitsParams,
+    ) -> Result<()> {
+        instructions::set_protocol_limits_handler(ctx, params)
+    }
+
+    /// Pauses or unpauses parts of the protocol
+    ///
+    /// Only the pauser can change the pause flags. The withdraw only mode pauses
+    /// every subsystem but keeps unstaking open so stakers can exit.
+    ///
+    /// # Arguments
+    /// * `paused` - Paused subsystems as bit flags: minting, messaging, responses, staking and config updates
+    /// * `withdraw_only` - Whether to pause everything except unstaking
+    pub fn set_protocol_pause(
+        ctx: Context<SetProtocolPause>,
+        paused: u8,
+        withdraw_only: bool,
+    ) -> Result<()> {
+        instructions::set_protocol_pause_handler(ctx, paused, withdraw_only)
+    }
+
+    /// Updates the fee charged per message
+    ///
+    /// Only the fee manager can update the message fee.
+    ///
+    /// # Arguments
+    /// * `message_fee` - Compute tokens charged per message, before the owner markup
+    pub fn set_message_fee(ctx: Context<SetMessageFee>, message_fee: u64) -> Result<()> {
+        instructions::set_message_fee_handler(ctx, message_fee)
+    }
+
+    /// Updates the parameters of an AI NFT collection
+    ///
+    /// Only the collection authority can update the collection. Parameters that
+    /// are not set are left unchanged.
+    ///
+    /// # Arguments
+    /// * `mint_price` - New price to mint each NFT
+    /// * `max_supply` - New maximum supply, not below the current mint count
+    /// * `default_execution_client` - New default execution client for minted NFTs
+    pub fn update_app_ainft(
+        ctx: Context<UpdateAppAiNft>,
+        update_ai_nft_params: state::UpdateAiNftParams,
+    ) -> Result<()> {
+        instructions::update_app_ainft_handler(ctx, update_ai_nft_params)
+    }
+
+    /// Proposes a new authority for an AI NFT collection
+    ///
+    /// The new authority takes over once it accepts the transfer. Proposing the
+    /// default pubkey cancels a pending transfer.
+    ///
+    /// # Arguments
+    /// * `new_authority` - The proposed authority
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority_transfer_handler(ctx, new_authority)
+    }
+
+    /// Accepts a pending authority transfer of an AI NFT collection
+    ///
+    /// Must be signed by the proposed authority.
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+        instructions::accept_authority_transfer_handler(ctx)
+    }
+
     /// Mints a new AI NFT
     ///
     /// Creates a new AI NFT with specified character configuration and metadata.
@@ -46,8 +153,235 @@ pub mod ainft {
     /// * `name` - Name of the AI NFT
     /// * `uri` - URI pointing to the NFT's metadata
     /// * `character_config` - JSON configuration defining the AI's personality and behavior
-    pub fn mint_ainft(ctx: Context<MintAiNft>, name: String, uri: String) -> Result<()> {
-        instructions::mint_ainft_handler(ctx, name, uri)
+    /// * `allowlist_proof` - Merkle proof of the payer for an allowlist mint phase
+    pub fn mint_ainft(
+        ctx: Context<MintAiNft>,
+        name: String,
+        uri: String,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::mint_ainft_handler(ctx, name, uri, allowlist_proof)
+    }
+
+    /// Mints a new AI NFT that is ready to chat
+    ///
+    /// Mints the AI NFT, sets its character configuration and execution client
+    /// and creates its compute token account in a single instruction.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the AI NFT
+    /// * `uri` - URI pointing to the NFT's metadata
+    /// * `character_config` - Initial character configuration
+    /// * `initial_compute_deposit` - Compute tokens to transfer from the payer to the AI NFT
+    /// * `allowlist_proof` - Merkle proof of the payer for an allowlist mint phase
+    pub fn mint_ainft_with_config(
+        ctx: Context<MintAiNftWithConfig>,
+        name: String,
+        uri: String,
+        character_config: CharacterConfigInput,
+        initial_compute_deposit: Option<u64>,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::mint_ainft_with_config_handler(
+            ctx,
+            name,
+            uri,
+            character_config,
+            initial_compute_deposit,
+            allowlist_proof,
+        )
+    }
+
+    /// Forks an AI NFT
+    ///
+    /// Mints a new AI NFT whose character configuration is copied from a parent
+    /// AI character that allows forking. The fork fee is paid in compute tokens
+    /// to the owner of the parent, and the fork records its parent.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the forked AI NFT
+    /// * `uri` - URI pointing to the NFT's metadata
+    /// * `allowlist_proof` - Merkle proof of the payer for an allowlist mint phase
+    pub fn fork_ainft(
+        ctx: Context<ForkAiNft>,
+        name: String,
+        uri: String,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::fork_ainft_handler(ctx, name, uri, allowlist_proof)
+    }
+
+    /// Sets whether an AI character can be forked and the fork fee
+    ///
+    /// Only the owner of the AI NFT can change the fork settings.
+    ///
+    /// # Arguments
+    /// * `fork_allowed` - Whether others can fork the AI character
+    /// * `fork_fee` - Compute tokens paid to the owner for each fork
+    pub fn set_fork_settings(
+        ctx: Context<SetForkSettings>,
+        fork_allowed: bool,
+        fork_fee: u64,
+    ) -> Result<()> {
+        instructions::set_fork_settings_handler(ctx, fork_allowed, fork_fee)
+    }
+
+    /// Renames an AI character
+    ///
+    /// Releases the registry entry of the current name and claims the new one,
+    /// so names stay unique within the collection. Updates the character, its
+    /// config name and the NFT metadata name.
+    ///
+    /// # Arguments
+    /// * `name` - The new name, at most 32 bytes
+    pub fn rename_character(ctx: Context<RenameCharacter>, name: String) -> Result<()> {
+        instructions::rename_character_handler(ctx, name)
+    }
+
+    /// Updates the metadata URI of an AI NFT
+    ///
+    /// Lets the NFT holder point the NFT to new metadata, e.g. to change the
+    /// avatar image. The collection signs as the metadata update authority.
+    ///
+    /// # Arguments
+    /// * `uri` - URI pointing to the new metadata, at most 200 characters
+    pub fn update_character_metadata(
+        ctx: Context<UpdateCharacterMetadata>,
+        uri: String,
+    ) -> Result<()> {
+        instructions::update_character_metadata_handler(ctx, uri)
+    }
+
+    /// Claims an AI character after the NFT was transferred
+    ///
+    /// Records the NFT holder as the owner of the AI character. Ownership checks
+    /// still go through the NFT token account, the recorded owner marks when the
+    /// character changed hands.
+    ///
+    /// # Arguments
+    /// * `reset_owner_state` - Whether to clear the previous owner's fork settings and owner markup
+    pub fn claim_character(ctx: Context<ClaimCharacter>, reset_owner_state: bool) -> Result<()> {
+        instructions::claim_character_handler(ctx, reset_owner_state)
+    }
+
+    /// Sets the owner markup of an AI character
+    ///
+    /// Senders pay the markup in compute tokens on top of the message fee. It is
+    /// moved to the character's owner revenue account when the response is written.
+    ///
+    /// # Arguments
+    /// * `owner_markup` - Compute tokens charged per message for the owner
+    pub fn set_owner_markup(ctx: Context<SetOwnerMarkup>, owner_markup: u64) -> Result<()> {
+        instructions::set_owner_markup_handler(ctx, owner_markup)
+    }
+
+    /// Claims the owner revenue of an AI character
+    ///
+    /// Transfers the collected markup to the holder, or to the renter of an active lease.
+    pub fn claim_owner_revenue(ctx: Context<ClaimOwnerRevenue>) -> Result<()> {
+        instructions::claim_owner_revenue_handler(ctx)
+    }
+
+    /// Creates a compute token mint for an AI character
+    ///
+    /// The AI character becomes the mint authority and senders pay the character
+    /// in its own token from then on. Remaining collection compute tokens of the
+    /// character are moved to the holder and the owner markup is cleared.
+    pub fn create_character_compute_mint(ctx: Context<CreateCharacterComputeMint>) -> Result<()> {
+        instructions::create_character_compute_mint_handler(ctx)
+    }
+
+    /// Mints compute tokens of an AI character's own compute mint
+    ///
+    /// # Arguments
+    /// * `amount` - Tokens to mint to the destination token account
+    pub fn mint_character_compute(ctx: Context<MintCharacterCompute>, amount: u64) -> Result<()> {
+        instructions::mint_character_compute_handler(ctx, amount)
+    }
+
+    /// Offers an AI character for rent
+    ///
+    /// The NFT holder sets the rent and the lease duration. Once rented, the
+    /// renter holds the rights of the NFT holder until the lease expires:
+    /// editing the config, choosing the execution client and receiving revenue.
+    ///
+    /// # Arguments
+    /// * `rent_price` - Rent in compute tokens, paid up front to the holder
+    /// * `duration` - Lease duration in seconds
+    pub fn offer_lease(ctx: Context<OfferLease>, rent_price: u64, duration: i64) -> Result<()> {
+        instructions::offer_lease_handler(ctx, rent_price, duration)
+    }
+
+    /// Rents an offered AI character
+    ///
+    /// Pays the rent in compute tokens to the lessor and starts the lease.
+    pub fn rent_character(ctx: Context<RentCharacter>) -> Result<()> {
+        instructions::rent_character_handler(ctx)
+    }
+
+    /// Closes a lease that was not rented or has expired
+    ///
+    /// Either the lessor or the current NFT holder can close it, so a lease
+    /// left behind by a previous holder doesn't block new offers. The rent
+    /// of the lease account goes back to the lessor.
+    pub fn close_lease(ctx: Context<CloseLease>) -> Result<()> {
+        instructions::close_lease_handler(ctx)
+    }
+
+    /// Lists an AI NFT for sale
+    ///
+    /// Moves the NFT into an escrow owned by the listing until it is sold or
+    /// the listing is cancelled. A character can't be listed during an active
+    /// lease, and its accrued owner revenue is paid out to the seller.
+    ///
+    /// # Arguments
+    /// * `price` - Price in lamports, or in compute tokens
+    /// * `pay_in_compute` - Whether the price is paid in the collection's compute mint
+    pub fn list_character(
+        ctx: Context<ListCharacter>,
+        price: u64,
+        pay_in_compute: bool,
+    ) -> Result<()> {
+        instructions::list_character_handler(ctx, price, pay_in_compute)
+    }
+
+    /// Cancels a listing and returns the NFT to the seller
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        instructions::cancel_listing_handler(ctx)
+    }
+
+    /// Buys a listed AI NFT
+    ///
+    /// Pays the royalties configured in the metadata to its creators, passed as
+    /// remaining accounts, and the rest of the price to the seller. The buyer
+    /// receives the NFT with the AI character's config and compute balance.
+    pub fn buy_character<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyCharacter<'info>>,
+    ) -> Result<()> {
+        instructions::buy_character_handler(ctx)
+    }
+
+    /// Burns an AI NFT
+    ///
+    /// Burns the NFT and closes its metadata, the AI character account and its
+    /// compute token account. The remaining compute tokens and the rent go back
+    /// to the owner. Fails while messages to the AI character are unanswered.
+    pub fn burn_ainft(ctx: Context<BurnAiNft>) -> Result<()> {
+        instructions::burn_ainft_handler(ctx)
+    }
+
+    /// Sets the character template of an AI NFT collection
+    ///
+    /// Newly minted AI NFTs start from this configuration instead of the
+    /// built in default. Can only be called by the collection authority.
+    ///
+    /// # Arguments
+    /// * `character_config` - The template character configuration
+    pub fn update_character_template(
+        ctx: Context<UpdateCharacterTemplate>,
+        character_config: CharacterConfigInput,
+    ) -> Result<()> {
+        instructions::update_character_template_handler(ctx, character_config)
     }
 
     /// Creates a compute mint for an AI NFT
@@ -63,6 +397,88 @@ pub mod ainft {
         instructions::set_external_compute_mint_handler(ctx)
     }
 
+    /// Updates the royalties of a collection
+    ///
+    /// Only the collection authority can update the royalties. The new royalties
+    /// apply to NFTs minted after the update.
+    ///
+    /// # Arguments
+    /// * `royalties` - Royalty basis points, creator split and minter share
+    pub fn update_royalties(
+        ctx: Context<UpdateRoyalties>,
+        royalties: state::RoyaltyConfig,
+    ) -> Result<()> {
+        instructions::update_royalties_handler(ctx, royalties)
+    }
+
+    /// Sets the mint phases of a collection
+    ///
+    /// Each phase has a schedule, a price, a per-wallet cap and an optional
+    /// Merkle root for allowlists. Phases must be sorted and must not overlap.
+    /// Without phases the collection mints at its mint price.
+    ///
+    /// # Arguments
+    /// * `mint_phases` - The mint phases, replacing the current ones
+    pub fn set_mint_phases(
+        ctx: Context<SetMintPhases>,
+        mint_phases: Vec<state::MintPhase>,
+    ) -> Result<()> {
+        instructions::set_mint_phases_handler(ctx, mint_phases)
+    }
+
+    /// Sets the treasury recipients of a collection's mint proceeds
+    ///
+    /// Only the collection authority can set the recipients. An empty list
+    /// sends the proceeds to the authority.
+    ///
+    /// # Arguments
+    /// * `recipients` - Recipient addresses and their shares in basis points, adding up to 10000
+    pub fn set_treasury_recipients(
+        ctx: Context<SetTreasuryRecipients>,
+        recipients: Vec<state::TreasuryRecipient>,
+    ) -> Result<()> {
+        instructions::set_treasury_recipients_handler(ctx, recipients)
+    }
+
+    /// Withdraws the mint proceeds held by the collection
+    ///
+    /// Splits the withdrawn lamports between the treasury recipients, passed as
+    /// remaining accounts in the configured order. The collection account is
+    /// kept rent exempt. When the treasury token account is passed, withdraws
+    /// the proceeds paid in the payment mint to the recipients' token accounts.
+    ///
+    /// # Arguments
+    /// * `amount` - Lamports or tokens to withdraw, or everything available if not set
+    pub fn withdraw_mint_proceeds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawMintProceeds<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::withdraw_mint_proceeds_handler(ctx, amount)
+    }
+
+    /// Sets the protocol fee of the collection
+    ///
+    /// The fee is deducted from every settled response into the protocol
+    /// treasury, before the staker and client portions. Creates the protocol
+    /// treasury token account on first use.
+    ///
+    /// # Arguments
+    /// * `protocol_fee_bps` - Fee in basis points, at most `MAX_PROTOCOL_FEE_BPS`
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, protocol_fee_bps: u16) -> Result<()> {
+        instructions::set_protocol_fee_handler(ctx, protocol_fee_bps)
+    }
+
+    /// Withdraws compute tokens from the protocol treasury
+    ///
+    /// # Arguments
+    /// * `amount` - Tokens to withdraw, or everything available if not set
+    pub fn withdraw_protocol_fees(
+        ctx: Context<WithdrawProtocolFees>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::withdraw_protocol_fees_handler(ctx, amount)
+    }
+
     /// Sends a message to an AI NFT
     ///
     /// Allows users to interact with an AI NFT by sending messages.
@@ -133,6 +549,20 @@ pub mod ainft {
         instructions::update_execution_client_config_handler(ctx, new_gas)
     }
 
+    /// Sets the character compute mints an execution client accepts
+    ///
+    /// The collection compute mint is always accepted. Responses of characters
+    /// with their own compute mint are paid to the client in that mint.
+    ///
+    /// # Arguments
+    /// * `compute_mints` - Accepted character compute mints, at most `MAX_ACCEPTED_COMPUTE_MINTS`
+    pub fn set_accepted_compute_mints(
+        ctx: Context<SetAcceptedComputeMints>,
+        compute_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_accepted_compute_mints_handler(ctx, compute_mints)
+    }
+
     /// Updates an AI NFT's character configuration
     ///
     /// Allows NFT owners to update their AI's personality and behavior settings.
@@ -146,6 +576,28 @@ pub mod ainft {
         instructions::update_character_config_handler(ctx, character_config)
     }
 
+    /// Rolls an AI NFT's character configuration back to a previous version
+    ///
+    /// The current configuration is kept in the history, so the rollback itself
+    /// can be undone.
+    ///
+    /// # Arguments
+    /// * `version` - The config version to restore
+    pub fn rollback_character_config(
+        ctx: Context<UpdateCharacterConfigField>,
+        version: u64,
+    ) -> Result<()> {
+        instructions::rollback_character_config_handler(ctx, version)
+    }
+
+    /// Permanently freezes an AI NFT's character configuration
+    ///
+    /// Can only be called once by the NFT owner. All later config updates
+    /// and rollbacks are rejected.
+    pub fn freeze_character_config(ctx: Context<FreezeCharacterConfig>) -> Result<()> {
+        instructions::freeze_character_config_handler(ctx)
+    }
+
     /// Updates an AI NFT's execution client
     pub fn update_ai_character_execution_client(
         ctx: Context<UpdateAiCharacterExecutionClient>,
@@ -160,14 +612,6 @@ pub mod ainft {
         instructions::create_ai_character_compute_account_handler(ctx)
     }
 
-    /// Updates an AI NFT's character name
-    pub fn update_character_name(
-        ctx: Context<UpdateCharacterConfigField>,
-        name: String,
-    ) -> Result<()> {
-        instructions::update_character_name_handler(ctx, name)
-    }
-
     /// Updates an AI NFT's supported clients
     pub fn update_character_clients(
         ctx: Context<UpdateCharacterConfigField>,
@@ -192,6 +636,34 @@ pub mod ainft {
         instructions::update_character_voice_settings_handler(ctx, model)
     }
 
+    /// Updates an AI NFT's model settings
+    ///
+    /// Tells execution clients which model, sampling parameters and system
+    /// prompt to use for the AI.
+    pub fn update_character_model_settings(
+        ctx: Context<UpdateCharacterConfigField>,
+        model_settings: ModelSettingsInput,
+    ) -> Result<()> {
+        instructions::update_character_model_settings_handler(ctx, model_settings)
+    }
+
+    /// Updates an AI NFT's system prompt reference
+    ///
+    /// # Arguments
+    /// * `system_prompt_hash` - Hash of the off chain system prompt
+    /// * `system_prompt_uri` - URI the system prompt can be fetched from
+    pub fn update_character_system_prompt(
+        ctx: Context<UpdateCharacterConfigField>,
+        system_prompt_hash: [u8; 32],
+        system_prompt_uri: String,
+    ) -> Result<()> {
+        instructions::update_character_system_prompt_handler(
+            ctx,
+            system_prompt_hash,
+            system_prompt_uri,
+        )
+    }
+
     /// Updates an AI NFT's bio
     pub fn update_character_bio(
         ctx: Context<UpdateCharacterConfigField>,
@@ -263,4 +735,17 @@ pub mod ainft {
     ) -> Result<()> {
         instructions::update_character_style_post_handler(ctx, style_post)
     }
+
+    /// Updates several AI NFT config fields in one instruction
+    ///
+    /// # Arguments
+    /// * `field_mask` - Bit mask of the fields to update, see `config_fields`
+    /// * `values` - One value per set bit, ordered from the lowest bit to the highest
+    pub fn update_character_fields(
+        ctx: Context<UpdateCharacterConfigField>,
+        field_mask: u32,
+        values: Vec<ConfigFieldValue>,
+    ) -> Result<()> {
+        instructions::update_character_fields_handler(ctx, field_mask, values)
+    }
 }
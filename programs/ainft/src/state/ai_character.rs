@@ -1,7 +1,51 @@
-use anchor_lang::{prelude::*, Bump};
+use anchor_lang::{prelude::*, solana_program::hash::hash, Bump};
 
 use crate::error::AiNftError;
 
+use super::CharacterConfigHistory;
+
+/// Bit flags identifying the character config fields touched by an update
+pub mod config_fields {
+    pub const NAME: u32 = 1 << 0;
+    pub const CLIENTS: u32 = 1 << 1;
+    pub const MODEL_PROVIDER: u32 = 1 << 2;
+    pub const VOICE_SETTINGS: u32 = 1 << 3;
+    pub const BIO: u32 = 1 << 4;
+    pub const LORE: u32 = 1 << 5;
+    pub const KNOWLEDGE: u32 = 1 << 6;
+    pub const TOPICS: u32 = 1 << 7;
+    pub const STYLE_ALL: u32 = 1 << 8;
+    pub const STYLE_CHAT: u32 = 1 << 9;
+    pub const STYLE_POST: u32 = 1 << 10;
+    pub const ADJECTIVES: u32 = 1 << 11;
+    pub const MODEL_SETTINGS: u32 = 1 << 12; // includes the system prompt hash and uri
+
+    pub const STYLE: u32 = STYLE_ALL | STYLE_CHAT | STYLE_POST;
+    pub const ALL: u32 = (1 << 13) - 1;
+    /// Fields replaced by a full config update or rollback, which keep the name
+    pub const ALL_BUT_NAME: u32 = ALL & !NAME;
+
+    /// Field name as used by `CharacterConfig::update_field` and `update_array_field`
+    pub fn name(field: u32) -> Option<&'static str> {
+        match field {
+            NAME => Some("name"),
+            CLIENTS => Some("clients"),
+            MODEL_PROVIDER => Some("model_provider"),
+            VOICE_SETTINGS => Some("voice_settings"),
+            BIO => Some("bio"),
+            LORE => Some("lore"),
+            KNOWLEDGE => Some("knowledge"),
+            TOPICS => Some("topics"),
+            STYLE_ALL => Some("style_all"),
+            STYLE_CHAT => Some("style_chat"),
+            STYLE_POST => Some("style_post"),
+            ADJECTIVES => Some("adjectives"),
+            MODEL_SETTINGS => Some("model_settings"),
+            _ => None,
+        }
+    }
+}
+
 #[account(zero_copy(unsafe))]
 #[derive(Default)]
 pub struct VoiceSettings {
@@ -28,6 +72,56 @@ impl From<SettingsInput> for Settings {
     }
 }
 
+/// Model parameters the execution client should use for the character.
+/// Zeroed values leave the choice to the execution client.
+#[account(zero_copy(unsafe))]
+pub struct ModelSettings {
+    pub model: [u8; 32],                  // model id, e.g. claude-3-5-sonnet
+    pub temperature: u16,                 // in hundredths, 70 = 0.7
+    pub top_p: u16,                       // in hundredths, 95 = 0.95
+    pub max_tokens: u32,                  // max tokens per response
+    pub context_window: u32,              // max tokens of context per request
+    pub stop_sequences: [[u8; 16]; 4],    // each stop sequence is max 16 characters
+    pub system_prompt_hash: [u8; 32],     // hash of the off chain system prompt
+    pub system_prompt_uri: [u8; 64],      // where the system prompt can be fetched
+}
+
+impl Default for ModelSettings {
+    fn default() -> Self {
+        bytemuck::Zeroable::zeroed()
+    }
+}
+
+impl ModelSettings {
+    pub const MAX_TEMPERATURE: u16 = 200;
+    pub const MAX_TOP_P: u16 = 100;
+
+    pub fn update_system_prompt(&mut self, hash: [u8; 32], uri: &str) {
+        self.system_prompt_hash = hash;
+        self.system_prompt_uri = CharacterConfig::init_byte_array::<64, 1>(uri)[0];
+    }
+}
+
+impl From<ModelSettingsInput> for ModelSettings {
+    fn from(input: ModelSettingsInput) -> Self {
+        let mut stop_sequences = [[0u8; 16]; 4];
+        for (i, stop_sequence) in input.stop_sequences.iter().enumerate().take(4) {
+            stop_sequences[i] = CharacterConfig::init_byte_array::<16, 1>(stop_sequence)[0];
+        }
+        Self {
+            model: CharacterConfig::init_byte_array::<32, 1>(&input.model)[0],
+            temperature: input.temperature,
+            top_p: input.top_p,
+            max_tokens: input.max_tokens,
+            context_window: input.context_window,
+            stop_sequences,
+            system_prompt_hash: input.system_prompt_hash,
+            system_prompt_uri: CharacterConfig::init_byte_array::<64, 1>(&input.system_prompt_uri)
+                [0],
+        }
+    }
+}
+
 #[account(zero_copy(unsafe), Copy)]
 #[derive(Default)]
 pub struct StyleConfig {
@@ -55,6 +149,7 @@ pub struct CharacterConfig {
     pub clients: [[u8; 20]; 10],
     pub model_provider: [u8; 20],
     pub settings: Settings,
+    pub model_settings: ModelSettings,
     pub bio: [[u8; 32]; 10],
     pub lore: [[u8; 32]; 10],
     pub knowledge: [[u8; 32]; 10],
@@ -64,54 +159,26 @@ pub struct CharacterConfig {
 }
 
 impl CharacterConfig {
+    /// Hash of the raw config bytes, used to identify a config version
+    pub fn hash(&self) -> [u8; 32] {
+        hash(bytemuck::bytes_of(self)).to_bytes()
+    }
+
+    /// Copies the input into the fixed size config arrays, zero padded.
+    /// Call `CharacterConfigInput::validate` first, longer values are truncated.
     pub fn from_input(input: CharacterConfigInput) -> Self {
         Self {
-            name: input.name.as_bytes().try_into().unwrap(),
-            clients: input
-                .clients
-                .iter()
-                .map(|c| c.as_bytes().try_into().unwrap())
-                .collect::<Vec<[u8; 20]>>()
-                .try_into()
-                .unwrap(),
-            model_provider: input.model_provider.as_bytes().try_into().unwrap(),
+            name: Self::init_byte_array::<32, 1>(&input.name)[0],
+            clients: Self::init_byte_arrays(&input.clients),
+            model_provider: Self::init_byte_array::<20, 1>(&input.model_provider)[0],
             settings: input.settings.into(),
-            bio: input
-                .bio
-                .iter()
-                .map(|b| b.as_bytes().try_into().unwrap())
-                .collect::<Vec<[u8; 32]>>()
-                .try_into()
-                .unwrap(),
-            lore: input
-                .lore
-                .iter()
-                .map(|l| l.as_bytes().try_into().unwrap())
-                .collect::<Vec<[u8; 32]>>()
-                .try_into()
-                .unwrap(),
-            knowledge: input
-                .knowledge
-                .iter()
-                .map(|k| k.as_bytes().try_into().unwrap())
-                .collect::<Vec<[u8; 32]>>()
-                .try_into()
-                .unwrap(),
-            topics: input
-                .topics
-                .iter()
-                .map(|t| t.as_bytes().try_into().unwrap())
-                .collect::<Vec<[u8; 32]>>()
-                .try_into()
-                .unwrap(),
+            model_settings: input.model_settings.into(),
+            bio: Self::init_byte_arrays(&input.bio),
+            lore: Self::init_byte_arrays(&input.lore),
+            knowledge: Self::init_byte_arrays(&input.knowledge),
+            topics: Self::init_byte_arrays(&input.topics),
             style: input.style.into(),
-            adjectives: input
-                .adjectives
-                .iter()
-                .map(|a| a.as_bytes().try_into().unwrap())
-                .collect::<Vec<[u8; 32]>>()
-                .try_into()
-                .unwrap(),
+            adjectives: Self::init_byte_arrays(&input.adjectives),
         }
     }
 
@@ -148,6 +215,10 @@ impl CharacterConfig {
         self.settings.voice.model = model;
     }
 
+    pub fn update_model_settings(&mut self, model_settings: ModelSettingsInput) {
+        self.model_settings = model_settings.into();
+    }
+
     pub fn update_bio(&mut self, bio: Vec<String>) {
         for (i, text) in bio.iter().enumerate().take(10) {
             let bytes = text.as_bytes();
@@ -242,6 +313,57 @@ impl CharacterConfig {
         }
         Ok(())
     }
+
+    // Helper method to update a fixed size field while keeping others unchanged
+    pub fn update_bytes_field(&mut self, field_name: &str, value: ConfigFieldValue) -> Result<()> {
+        match (field_name, value) {
+            ("voice_settings", ConfigFieldValue::Bytes(model)) => self.update_voice_settings(model),
+            ("style_all", ConfigFieldValue::BytesList(style)) => self.update_style_all(*style),
+            ("style_chat", ConfigFieldValue::BytesList(style)) => self.update_style_chat(*style),
+            ("style_post", ConfigFieldValue::BytesList(style)) => self.update_style_post(*style),
+            _ => return Err(error!(AiNftError::InvalidConfigField)),
+        }
+        Ok(())
+    }
+
+    /// Updates every field set in `field_mask` (see `config_fields`).
+    /// `values` holds one value per set bit, ordered from the lowest bit to the highest.
+    pub fn update_fields(&mut self, field_mask: u32, values: Vec<ConfigFieldValue>) -> Result<()> {
+        require!(
+            field_mask != 0 && field_mask & !config_fields::ALL == 0,
+            AiNftError::InvalidConfigField
+        );
+        // the name is tied to the name registry and metadata, only rename_character changes it
+        require!(
+            field_mask & config_fields::NAME == 0,
+            AiNftError::InvalidConfigField
+        );
+        require!(
+            values.len() == field_mask.count_ones() as usize,
+            AiNftError::InvalidConfigField
+        );
+
+        let fields = (0..u32::BITS)
+            .map(|bit| 1 << bit)
+            .filter(|field| field_mask & field != 0);
+        for (field, value) in fields.zip(values) {
+            let field_name = config_fields::name(field).ok_or(AiNftError::InvalidConfigField)?;
+            match value {
+                ConfigFieldValue::Text(value) => self.update_field(field_name, value)?,
+                ConfigFieldValue::TextList(values) => self.update_array_field(field_name, values)?,
+                ConfigFieldValue::ModelSettings(model_settings) => {
+                    require!(
+                        field == config_fields::MODEL_SETTINGS,
+                        AiNftError::InvalidConfigField
+                    );
+                    model_settings.validate()?;
+                    self.update_model_settings(*model_settings);
+                }
+                value => self.update_bytes_field(field_name, value)?,
+            }
+        }
+        Ok(())
+    }
 }
 
 /// voice settings input for the character config
@@ -256,6 +378,37 @@ pub struct SettingsInput {
     pub voice: VoiceSettingsInput,
 }
 
+/// model settings input for the character config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ModelSettingsInput {
+    pub model: String,
+    pub temperature: u16,
+    pub top_p: u16,
+    pub max_tokens: u32,
+    pub context_window: u32,
+    pub stop_sequences: Vec<String>,
+    pub system_prompt_hash: [u8; 32],
+    pub system_prompt_uri: String,
+}
+
+impl ModelSettingsInput {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.model.len() <= 32, AiNftError::ConfigTooLong);
+        require!(self.system_prompt_uri.len() <= 64, AiNftError::ConfigTooLong);
+        require!(self.stop_sequences.len() <= 4, AiNftError::ConfigTooLong);
+        require!(
+            self.stop_sequences.iter().all(|s| s.len() <= 16),
+            AiNftError::ConfigTooLong
+        );
+        require!(
+            self.temperature <= ModelSettings::MAX_TEMPERATURE
+                && self.top_p <= ModelSettings::MAX_TOP_P,
+            AiNftError::InvalidModelSettings
+        );
+        Ok(())
+    }
+}
+
 /// style config input for the character config
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Default)]
 pub struct StyleConfigInput {
@@ -264,6 +417,16 @@ pub struct StyleConfigInput {
     pub post: [[u8; 32]; 10],
 }
 
+/// value of a single character config field in a multi field update
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub enum ConfigFieldValue {
+    Text(String),             // name, model_provider
+    TextList(Vec<String>),    // clients, bio, lore, knowledge, topics, adjectives
+    Bytes([u8; 32]),          // voice_settings
+    BytesList(Box<[[u8; 32]; 10]>), // style_all, style_chat, style_post
+    ModelSettings(Box<ModelSettingsInput>), // model_settings
+}
+
 // struct for sending in the character config
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Default)]
 pub struct CharacterConfigInput {
@@ -271,6 +434,7 @@ pub struct CharacterConfigInput {
     pub clients: Vec<String>,
     pub model_provider: String,
     pub settings: SettingsInput,
+    pub model_settings: ModelSettingsInput,
     pub bio: Vec<String>,
     pub lore: Vec<String>,
     pub knowledge: Vec<String>,
@@ -279,6 +443,30 @@ pub struct CharacterConfigInput {
     pub adjectives: Vec<String>,
 }
 
+impl CharacterConfigInput {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.name.len() <= 32, AiNftError::ConfigTooLong);
+        require!(self.model_provider.len() <= 20, AiNftError::ConfigTooLong);
+        require!(
+            self.clients.len() <= 10 && self.clients.iter().all(|c| c.len() <= 20),
+            AiNftError::ConfigTooLong
+        );
+        for values in [
+            &self.bio,
+            &self.lore,
+            &self.knowledge,
+            &self.topics,
+            &self.adjectives,
+        ] {
+            require!(
+                values.len() <= 10 && values.iter().all(|v| v.len() <= 32),
+                AiNftError::ConfigTooLong
+            );
+        }
+        self.model_settings.validate()
+    }
+}
+
 impl CharacterConfig {
     fn init_byte_array<const N: usize, const M: usize>(text: &str) -> [[u8; N]; M] {
         let mut arr = [[0u8; N]; M];
@@ -288,6 +476,14 @@ impl CharacterConfig {
         arr
     }
 
+    fn init_byte_arrays<const N: usize, const M: usize>(texts: &[String]) -> [[u8; N]; M] {
+        let mut arr = [[0u8; N]; M];
+        for (entry, text) in arr.iter_mut().zip(texts) {
+            *entry = Self::init_byte_array::<N, 1>(text)[0];
+        }
+        arr
+    }
+
     pub fn default() -> Self {
         Self {
             name: Self::init_byte_array::<32, 1>("Default Assistant")[0],
@@ -298,6 +494,7 @@ impl CharacterConfig {
                     model: Self::init_byte_array::<32, 1>("en_GB-danny-low")[0],
                 },
             },
+            model_settings: ModelSettings::default(),
             bio: Self::init_byte_array::<32, 10>("A helpful AI assistant"),
             lore: Self::init_byte_array::<32, 10>("Created to help users with their tasks"),
             knowledge: Self::init_byte_array::<32, 10>("General assistance"),
@@ -324,6 +521,17 @@ pub struct AiCharacterNFT {
     pub total_processed: u64, // 8 bytes
     pub compute_token_account: Pubkey,
     pub execution_client: Pubkey, // 32 bytes
+    pub config_version: u64,      // 8 bytes, bumped on every config change
+    pub previous_config_hash: [u8; 32], // 32 bytes, hash of the config before the last change
+    pub config_frozen: u8,        // 1 byte, 1 once the config can no longer change
+    pub frozen_config_hash: [u8; 32], // 32 bytes, hash of the config at freeze time
+    pub fork_allowed: u8,         // 1 byte, 1 if the owner allows forking the character
+    pub fork_fee: u64,            // 8 bytes, compute tokens paid to the owner for a fork
+    pub parent_character: Pubkey, // 32 bytes, character this one was forked from (default if none)
+    pub owner: Pubkey,            // 32 bytes, holder that last claimed the character
+    pub owner_since: i64,         // 8 bytes, timestamp of the last claim
+    pub owner_markup: u64,        // 8 bytes, compute tokens charged per message for the owner
+    pub character_compute_mint: Pubkey, // 32 bytes, own compute mint of the character (default for the collection mint)
     pub bump: [u8; 1],            // 1 byte
 }
 
@@ -357,6 +565,17 @@ impl AiCharacterNFT {
             execution_client: *execution_client,
             compute_token_account: compute_token_account.unwrap(),
             character_config: CharacterConfig::default(),
+            config_version: 0,
+            previous_config_hash: [0u8; 32],
+            config_frozen: 0,
+            frozen_config_hash: [0u8; 32],
+            fork_allowed: 0,
+            fork_fee: 0,
+            parent_character: Pubkey::default(),
+            owner: Pubkey::default(),
+            owner_since: 0,
+            owner_markup: 0,
+            character_compute_mint: Pubkey::default(),
             bump: [bump; 1],
         }
     }
@@ -370,21 +589,172 @@ impl AiCharacterNFT {
         compute_token_account: Pubkey,
         bump: u8,
     ) {
-        let mut name_bytes = [0u8; 32];
-        let name_slice = name.as_bytes();
-        let len = std::cmp::min(name_slice.len(), 32);
-        name_bytes[..len].copy_from_slice(&name_slice[..len]);
         self.app_ai_nft_mint = *app_ai_nft_mint;
         self.character_nft_mint = *character_nft_mint;
-        self.name = name_bytes;
+        self.set_name(name);
         self.execution_client = *execution_client;
         self.compute_token_account = compute_token_account;
         self.total_processed = 0;
+        self.config_version = 0;
+        self.previous_config_hash = [0u8; 32];
+        self.config_frozen = 0;
+        self.frozen_config_hash = [0u8; 32];
+        self.fork_allowed = 0;
+        self.fork_fee = 0;
+        self.parent_character = Pubkey::default();
+        self.owner = Pubkey::default();
+        self.owner_since = 0;
+        self.owner_markup = 0;
+        self.character_compute_mint = Pubkey::default();
         self.bump = [bump; 1];
     }
 
+    pub fn set_name(&mut self, name: &str) {
+        let mut name_bytes = [0u8; 32];
+        let name_slice = name.as_bytes();
+        let len = std::cmp::min(name_slice.len(), 32);
+        name_bytes[..len].copy_from_slice(&name_slice[..len]);
+        self.name = name_bytes;
+    }
+
+    /// Renames the character, keeping the config name in sync
+    pub fn rename(&mut self, name: String) -> Result<()> {
+        self.set_name(&name);
+        self.update_config_name(name)
+    }
+
+    pub fn set_owner(&mut self, owner: Pubkey, now: i64) {
+        self.owner = owner;
+        self.owner_since = now;
+    }
+
+    /// Records a new holder of the character. `reset_owner_state` clears the
+    /// settings the previous owner made for themselves.
+    pub fn claim(&mut self, owner: Pubkey, now: i64, reset_owner_state: bool) -> Result<()> {
+        require!(self.owner != owner, AiNftError::CharacterAlreadyClaimed);
+        self.set_owner(owner, now);
+        if reset_owner_state {
+            self.set_fork_settings(false, 0);
+            self.owner_markup = 0;
+        }
+        Ok(())
+    }
+
+    pub fn is_fork_allowed(&self) -> bool {
+        self.fork_allowed != 0
+    }
+
+    pub fn set_fork_settings(&mut self, fork_allowed: bool, fork_fee: u64) {
+        self.fork_allowed = fork_allowed as u8;
+        self.fork_fee = fork_fee;
+    }
+
+    /// Starts the character as a fork of `parent`, copying its config
+    pub fn fork_from(&mut self, parent_key: Pubkey, parent: &AiCharacterNFT) {
+        let name = self.character_config.name;
+        self.character_config = parent.character_config;
+        self.character_config.name = name;
+        self.parent_character = parent_key;
+    }
+
+    /// The owner mints the character compute token, so a markup paid in it is
+    /// not revenue. Only characters on the collection compute mint have one.
+    pub fn set_owner_markup(&mut self, owner_markup: u64) -> Result<()> {
+        require!(
+            !self.has_compute_mint() || owner_markup == 0,
+            AiNftError::OwnerMarkupNotSupported
+        );
+        self.owner_markup = owner_markup;
+        Ok(())
+    }
+
+    pub fn has_compute_mint(&self) -> bool {
+        self.character_compute_mint != Pubkey::default()
+    }
+
+    /// Mint the character is paid in: its own compute mint, or otherwise the
+    /// collection compute mint
+    pub fn compute_mint_or(&self, collection_compute_mint: Pubkey) -> Pubkey {
+        if self.has_compute_mint() {
+            self.character_compute_mint
+        } else {
+            collection_compute_mint
+        }
+    }
+
+    /// Switches the character to its own compute mint. Pending messages were
+    /// paid in the collection mint, so they have to be answered first.
+    pub fn set_compute_mint(
+        &mut self,
+        compute_mint: Pubkey,
+        compute_token_account: Pubkey,
+    ) -> Result<()> {
+        require!(!self.has_compute_mint(), AiNftError::ComputeMintAlreadyCreated);
+        require!(!self.has_pending_messages(), AiNftError::PendingMessages);
+        self.character_compute_mint = compute_mint;
+        self.compute_token_account = compute_token_account;
+        self.owner_markup = 0;
+        Ok(())
+    }
+
+    /// Compute tokens a sender pays per message, the protocol message fee
+    /// plus the owner markup
+    pub fn message_price(&self, message_fee: u64) -> Result<u64> {
+        message_fee
+            .checked_add(self.owner_markup)
+            .ok_or(error!(AiNftError::Overflow))
+    }
+
+    pub fn is_config_frozen(&self) -> bool {
+        self.config_frozen != 0
+    }
+
+    /// Permanently locks the character config
+    pub fn freeze_config(&mut self) -> Result<()> {
+        require!(!self.is_config_frozen(), AiNftError::ConfigFrozen);
+        self.config_frozen = 1;
+        self.frozen_config_hash = self.character_config.hash();
+        Ok(())
+    }
+
+    /// Stores the current config in the history and bumps the config version.
+    /// Must be called before the config is changed.
+    pub fn checkpoint_config(&mut self, history: &mut CharacterConfigHistory) -> Result<()> {
+        require!(!self.is_config_frozen(), AiNftError::ConfigFrozen);
+        history.push(self.config_version, &self.character_config);
+        self.previous_config_hash = self.character_config.hash();
+        self.config_version += 1;
+        Ok(())
+    }
+
+    /// Restores the config stored in the history under `version`.
+    /// The current config takes its place in the history, so a rollback can be undone.
+    pub fn rollback_config(
+        &mut self,
+        history: &mut CharacterConfigHistory,
+        version: u64,
+    ) -> Result<()> {
+        require!(!self.is_config_frozen(), AiNftError::ConfigFrozen);
+        let slot = history
+            .find(version)
+            .ok_or(AiNftError::ConfigVersionNotFound)?;
+        self.previous_config_hash = self.character_config.hash();
+        history.snapshots[slot].version = self.config_version;
+        std::mem::swap(
+            &mut self.character_config,
+            &mut history.snapshots[slot].config,
+        );
+        // the name only changes through rename_character, keep the current one
+        self.character_config.name = history.snapshots[slot].config.name;
+        self.config_version += 1;
+        Ok(())
+    }
+
     pub fn update_character_config(&mut self, new_config: CharacterConfigInput) -> Result<()> {
+        new_config.validate()?;
+        let name = self.character_config.name;
         self.character_config = CharacterConfig::from_input(new_config);
+        self.character_config.name = name;
         Ok(())
     }
 
@@ -396,6 +766,13 @@ impl AiCharacterNFT {
         self.message_count = message_count;
     }
 
+    /// Messages are answered in order, so every message beyond the processed
+    /// count is still waiting for a response
+    pub fn has_pending_messages(&self) -> bool {
+        let (message_count, total_processed) = (self.message_count, self.total_processed);
+        message_count > total_processed
+    }
+
     pub fn update_total_processed(&mut self) {
         self.total_processed += 1;
     }
@@ -424,6 +801,20 @@ impl AiCharacterNFT {
         Ok(())
     }
 
+    pub fn update_config_model_settings(&mut self, model_settings: ModelSettingsInput) -> Result<()> {
+        model_settings.validate()?;
+        self.character_config.update_model_settings(model_settings);
+        Ok(())
+    }
+
+    pub fn update_config_system_prompt(&mut self, hash: [u8; 32], uri: String) -> Result<()> {
+        require!(uri.len() <= 64, AiNftError::ConfigTooLong);
+        self.character_config
+            .model_settings
+            .update_system_prompt(hash, &uri);
+        Ok(())
+    }
+
     pub fn update_config_bio(&mut self, bio: Vec<String>) -> Result<()> {
         self.character_config.update_bio(bio);
         Ok(())
@@ -468,4 +859,196 @@ impl AiCharacterNFT {
         self.character_config.update_style_post(style_post);
         Ok(())
     }
+
+    pub fn update_config_fields(
+        &mut self,
+        field_mask: u32,
+        values: Vec<ConfigFieldValue>,
+    ) -> Result<()> {
+        self.character_config.update_fields(field_mask, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_fields_with_mask() {
+        let mut config = CharacterConfig::default();
+        config
+            .update_fields(
+                config_fields::MODEL_PROVIDER | config_fields::BIO | config_fields::STYLE_CHAT,
+                vec![
+                    ConfigFieldValue::Text("openai".to_string()),
+                    ConfigFieldValue::TextList(vec!["New bio".to_string()]),
+                    ConfigFieldValue::BytesList(Box::new([[1u8; 32]; 10])),
+                ],
+            )
+            .unwrap();
+        assert_eq!(&config.model_provider[..6], b"openai");
+        assert_eq!(&config.bio[0][..7], b"New bio");
+        assert_eq!(config.style.chat, [[1u8; 32]; 10]);
+    }
+
+    #[test]
+    fn test_update_model_settings() {
+        let mut config = CharacterConfig::default();
+        config
+            .update_fields(
+                config_fields::MODEL_SETTINGS,
+                vec![ConfigFieldValue::ModelSettings(Box::new(ModelSettingsInput {
+                    model: "claude-3-5-sonnet".to_string(),
+                    temperature: 70,
+                    max_tokens: 1024,
+                    stop_sequences: vec!["###".to_string()],
+                    ..Default::default()
+                }))],
+            )
+            .unwrap();
+        assert_eq!(&config.model_settings.model[..17], b"claude-3-5-sonnet");
+        assert_eq!({ config.model_settings.temperature }, 70);
+        assert_eq!({ config.model_settings.max_tokens }, 1024);
+        assert_eq!(&config.model_settings.stop_sequences[0][..4], b"###\0");
+
+        let invalid = ModelSettingsInput {
+            temperature: ModelSettings::MAX_TEMPERATURE + 1,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_fields_rejects_mismatched_values() {
+        let mut config = CharacterConfig::default();
+        // missing value for the second field
+        assert!(config
+            .update_fields(
+                config_fields::MODEL_PROVIDER | config_fields::BIO,
+                vec![ConfigFieldValue::Text("openai".to_string())],
+            )
+            .is_err());
+        // wrong value kind for the field
+        assert!(config
+            .update_fields(
+                config_fields::BIO,
+                vec![ConfigFieldValue::Text("Agent".to_string())],
+            )
+            .is_err());
+        // the name only changes through rename_character
+        assert!(config
+            .update_fields(
+                config_fields::NAME,
+                vec![ConfigFieldValue::Text("Agent".to_string())],
+            )
+            .is_err());
+        // unknown field bit
+        assert!(config
+            .update_fields(1 << 20, vec![ConfigFieldValue::Bytes([0u8; 32])])
+            .is_err());
+    }
+
+    #[test]
+    fn test_config_from_input_pads_values() {
+        let input = CharacterConfigInput {
+            name: "Agent".to_string(),
+            clients: vec!["web".to_string()],
+            model_provider: "openai".to_string(),
+            bio: vec!["New bio".to_string(), "More bio".to_string()],
+            ..Default::default()
+        };
+        input.validate().unwrap();
+        let config = CharacterConfig::from_input(input);
+        assert_eq!(&config.name[..6], b"Agent\0");
+        assert_eq!(&config.clients[0][..4], b"web\0");
+        assert_eq!(config.clients[1], [0u8; 20]);
+        assert_eq!(&config.bio[1][..9], b"More bio\0");
+
+        let too_long = CharacterConfigInput {
+            bio: vec!["x".repeat(33)],
+            ..Default::default()
+        };
+        assert!(too_long.validate().is_err());
+    }
+
+    #[test]
+    fn test_message_price_includes_owner_markup() {
+        let mut ai_character = AiCharacterNFT::try_new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            "Agent",
+            &Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            255,
+        );
+        assert_eq!(ai_character.message_price(1).unwrap(), 1);
+
+        ai_character.set_owner_markup(4).unwrap();
+        assert_eq!(ai_character.message_price(1).unwrap(), 5);
+
+        ai_character.set_owner_markup(u64::MAX).unwrap();
+        assert!(ai_character.message_price(1).is_err());
+    }
+
+    #[test]
+    fn test_claim_resets_owner_state() {
+        let mut ai_character = AiCharacterNFT::try_new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            "Agent",
+            &Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            255,
+        );
+        ai_character.set_fork_settings(true, 10);
+        ai_character.set_owner_markup(4).unwrap();
+
+        // the owner settings are kept unless the claim resets them
+        ai_character.claim(Pubkey::new_unique(), 1, false).unwrap();
+        assert!(ai_character.is_fork_allowed());
+        assert_eq!({ ai_character.owner_markup }, 4);
+
+        let owner = Pubkey::new_unique();
+        ai_character.claim(owner, 2, true).unwrap();
+        assert!(!ai_character.is_fork_allowed());
+        assert_eq!({ ai_character.fork_fee }, 0);
+        assert_eq!({ ai_character.owner_markup }, 0);
+        assert!(ai_character.claim(owner, 3, true).is_err());
+    }
+
+    #[test]
+    fn test_set_compute_mint() {
+        let collection_compute_mint = Pubkey::new_unique();
+        let mut ai_character = AiCharacterNFT::try_new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            "Agent",
+            &Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            255,
+        );
+        ai_character.set_owner_markup(4).unwrap();
+        assert_eq!(
+            ai_character.compute_mint_or(collection_compute_mint),
+            collection_compute_mint
+        );
+
+        // pending messages are paid in the collection mint
+        ai_character.update_message_count(1);
+        let compute_mint = Pubkey::new_unique();
+        assert!(ai_character
+            .set_compute_mint(compute_mint, Pubkey::new_unique())
+            .is_err());
+
+        ai_character.update_total_processed();
+        ai_character
+            .set_compute_mint(compute_mint, Pubkey::new_unique())
+            .unwrap();
+        assert_eq!(ai_character.compute_mint_or(collection_compute_mint), compute_mint);
+        assert_eq!({ ai_character.owner_markup }, 0);
+        assert!(ai_character.set_owner_markup(1).is_err());
+        assert!(ai_character
+            .set_compute_mint(Pubkey::new_unique(), Pubkey::new_unique())
+            .is_err());
+    }
 }
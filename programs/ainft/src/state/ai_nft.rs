@@ -3,27 +3,47 @@ use anchor_spl::token::TokenAccount;
 
 use crate::error::AiNftError;
 
-use super::CreateAiNftParams;
+use super::{CreateAiNftParams, UpdateAiNftParams, MintPhase, MAX_MINT_PHASES, RoyaltyConfig, TreasuryRecipient, MAX_TREASURY_RECIPIENTS, TOTAL_SHARE_BPS, MAX_PROTOCOL_FEE_BPS};
 
 // AI NFT defines the collection of AI characters
 #[account]
 pub struct AiNft {
     // Base
+    pub creator: Pubkey,                  // Creator of the collection, part of the PDA seeds
+    pub collection_id: u64,               // Id of the collection per creator, part of the PDA seeds
     pub authority: Pubkey,                // NFT owner
+    pub pending_authority: Pubkey,        // Proposed new authority (default if none)
     pub master_metadata: Pubkey,          // Collection this NFT belongs to
     pub master_mint: Pubkey,              // NFT mint
     pub compute_mint: Pubkey,             // Compute token mint
     pub default_execution_client: Pubkey, // Default execution client
     pub mint_count: u64,                  // Number of NFTs minted
-    pub mint_price: u64,                  // Price in lamports
-    pub max_supply: u64,                  // Maximum number of mints (0 for unlimited)
+    pub mint_price: u64,                  // Price in lamports, or in payment mint tokens
+    pub payment_mint: Pubkey,             // Mint the price is paid in (default for SOL)
+    pub max_supply: u64,                  // Maximum number of mints
+    pub royalties: RoyaltyConfig,         // Royalties and creators of the minted characters
+    pub treasury_recipients: Vec<TreasuryRecipient>, // Split of the mint proceeds (empty pays the authority)
+    pub mint_phases: Vec<MintPhase>,      // Mint schedule (empty for an always open mint at mint_price)
+    pub mint_phases_version: u32,         // Bumped whenever the mint phases are replaced
+    pub protocol_fee_bps: u16,            // Share of every settled response kept in the protocol treasury
     pub bump: [u8; 1],
 }
 
 impl AiNft {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 16 + 8 + 1;
-    pub fn as_seeds(&self) -> [&[u8]; 2] {
-        ["app_ainft".as_bytes(), &self.bump]
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 32 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 16 + 8 + 32 + RoyaltyConfig::LEN
+        + 4 + MAX_TREASURY_RECIPIENTS * TreasuryRecipient::LEN
+        + 4 + MAX_MINT_PHASES * MintPhase::LEN
+        + 4
+        + 2
+        + 1;
+    pub fn as_seeds(&self) -> [&[u8]; 4] {
+        [
+            "app_ainft".as_bytes(),
+            self.creator.as_ref(),
+            // Solana targets are little endian, so this matches `collection_id.to_le_bytes()`
+            bytemuck::bytes_of(&self.collection_id),
+            &self.bump,
+        ]
     }
 
     pub fn try_new(
@@ -34,18 +54,134 @@ impl AiNft {
         params: &CreateAiNftParams,
     ) -> Result<Self> {
         Ok(Self {
+            creator: publisher,
+            collection_id: params.collection_id,
             authority: publisher,
+            pending_authority: Pubkey::default(),
             master_metadata,
             master_mint,
             compute_mint: Pubkey::default(),
             default_execution_client: params.default_execution_client,
             mint_count: 0,
             mint_price: params.mint_price,
+            payment_mint: params.payment_mint.unwrap_or_default(),
             max_supply: params.max_supply,
+            royalties: params.royalties.clone(),
+            treasury_recipients: Vec::new(),
+            mint_phases: Vec::new(),
+            mint_phases_version: 0,
+            protocol_fee_bps: 0,
             bump: [bump],
         })
     }
 
+    pub fn update(&mut self, params: UpdateAiNftParams) -> Result<()> {
+        if let Some(mint_price) = params.mint_price {
+            require!(mint_price > 0, AiNftError::InvalidMintPrice);
+            self.mint_price = mint_price;
+        }
+        if let Some(max_supply) = params.max_supply {
+            require!(
+                max_supply > 0 && max_supply >= self.mint_count,
+                AiNftError::InvalidSupply
+            );
+            self.max_supply = max_supply;
+        }
+        if let Some(default_execution_client) = params.default_execution_client {
+            self.default_execution_client = default_execution_client;
+        }
+        Ok(())
+    }
+
+    pub fn check_supply(&self) -> Result<()> {
+        require!(self.mint_count < self.max_supply, AiNftError::SupplyExceeded);
+        Ok(())
+    }
+
+    pub fn propose_authority(&mut self, pending_authority: Pubkey) {
+        self.pending_authority = pending_authority;
+    }
+
+    pub fn accept_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require!(
+            self.pending_authority != Pubkey::default()
+                && self.pending_authority == new_authority,
+            AiNftError::InvalidAuthority
+        );
+        self.authority = new_authority;
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn set_royalties(&mut self, royalties: RoyaltyConfig) -> Result<()> {
+        royalties.validate()?;
+        self.royalties = royalties;
+        Ok(())
+    }
+
+    pub fn set_protocol_fee(&mut self, protocol_fee_bps: u16) -> Result<()> {
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            AiNftError::InvalidProtocolFee
+        );
+        self.protocol_fee_bps = protocol_fee_bps;
+        Ok(())
+    }
+
+    pub fn set_treasury_recipients(&mut self, recipients: Vec<TreasuryRecipient>) -> Result<()> {
+        TreasuryRecipient::validate_list(&recipients)?;
+        self.treasury_recipients = recipients;
+        Ok(())
+    }
+
+    /// Splits `amount` between the treasury recipients. Rounding dust goes to
+    /// the last recipient so the payouts always add up to `amount`.
+    pub fn split_proceeds(&self, amount: u64) -> Vec<(Pubkey, u64)> {
+        if self.treasury_recipients.is_empty() {
+            return vec![(self.authority, amount)];
+        }
+        let mut remaining = amount;
+        let last = self.treasury_recipients.len() - 1;
+        self.treasury_recipients
+            .iter()
+            .enumerate()
+            .map(|(i, recipient)| {
+                let share = if i == last {
+                    remaining
+                } else {
+                    (amount as u128 * recipient.share_bps as u128 / TOTAL_SHARE_BPS as u128) as u64
+                };
+                remaining -= share;
+                (recipient.address, share)
+            })
+            .collect()
+    }
+
+    pub fn set_mint_phases(&mut self, mint_phases: Vec<MintPhase>) -> Result<()> {
+        MintPhase::validate_list(&mint_phases)?;
+        self.mint_phases = mint_phases;
+        // wallet mint counts of the replaced phases no longer apply
+        self.mint_phases_version = self.mint_phases_version.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Returns the index of the mint phase active at `now`, `None` if the
+    /// collection has no phases.
+    pub fn active_mint_phase(&self, now: i64) -> Result<Option<usize>> {
+        if self.mint_phases.is_empty() {
+            return Ok(None);
+        }
+        self.mint_phases
+            .iter()
+            .position(|phase| phase.is_active(now))
+            .map(Some)
+            .ok_or(error!(AiNftError::MintPhaseNotActive))
+    }
+
+    pub fn is_token_payment(&self) -> bool {
+        self.payment_mint != Pubkey::default()
+    }
+
     pub fn set_compute_mint(&mut self, compute_mint: Pubkey) {
         self.compute_mint = compute_mint;
     }
@@ -57,3 +193,60 @@ impl AiNft {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_ai_nft(treasury_recipients: Vec<TreasuryRecipient>) -> AiNft {
+        AiNft {
+            creator: Pubkey::new_unique(),
+            collection_id: 0,
+            authority: Pubkey::new_unique(),
+            pending_authority: Pubkey::default(),
+            master_metadata: Pubkey::default(),
+            master_mint: Pubkey::default(),
+            compute_mint: Pubkey::default(),
+            default_execution_client: Pubkey::default(),
+            mint_count: 0,
+            mint_price: 0,
+            payment_mint: Pubkey::default(),
+            max_supply: 0,
+            royalties: RoyaltyConfig::default(),
+            treasury_recipients,
+            mint_phases: Vec::new(),
+            mint_phases_version: 0,
+            protocol_fee_bps: 0,
+            bump: [0],
+        }
+    }
+
+    #[test]
+    fn test_check_supply() {
+        let mut ai_nft = new_ai_nft(vec![]);
+        ai_nft.max_supply = 2;
+        ai_nft.mint_count = 1;
+        assert!(ai_nft.check_supply().is_ok());
+        ai_nft.mint_count = 2;
+        assert!(ai_nft.check_supply().is_err());
+    }
+
+    #[test]
+    fn test_split_proceeds() {
+        let ai_nft = new_ai_nft(vec![]);
+        assert_eq!(ai_nft.split_proceeds(100), vec![(ai_nft.authority, 100)]);
+
+        let recipients = vec![
+            TreasuryRecipient { address: Pubkey::new_unique(), share_bps: 3_333 },
+            TreasuryRecipient { address: Pubkey::new_unique(), share_bps: 3_333 },
+            TreasuryRecipient { address: Pubkey::new_unique(), share_bps: 3_334 },
+        ];
+        TreasuryRecipient::validate_list(&recipients).unwrap();
+        let ai_nft = new_ai_nft(recipients);
+        let payouts = ai_nft.split_proceeds(1_000);
+        assert_eq!(
+            payouts.iter().map(|(_, share)| *share).collect::<Vec<_>>(),
+            vec![333, 333, 334]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::cell::RefMut;
+
+use anchor_lang::prelude::*;
+
+use super::CharacterConfig;
+
+/// Number of previous configs kept per character
+pub const CONFIG_HISTORY_LEN: usize = 3;
+
+#[account(zero_copy(unsafe))]
+#[derive(Default)]
+pub struct ConfigSnapshot {
+    pub version: u64,
+    pub config: CharacterConfig,
+}
+
+// Config history keeps the most recent configs of an AI character
+// so that the owner can roll back a bad edit
+#[account(zero_copy(unsafe))]
+pub struct CharacterConfigHistory {
+    pub ai_character: Pubkey,                          // 32 bytes
+    pub snapshot_count: u64,                           // 8 bytes, total snapshots written
+    pub snapshots: [ConfigSnapshot; CONFIG_HISTORY_LEN], // ring buffer of previous configs
+    pub bump: [u8; 1],                                 // 1 byte
+}
+
+impl CharacterConfigHistory {
+    /// Loads the history for writing. The account is created with `init_if_needed`,
+    /// so it is initialized here the first time it is used.
+    pub fn load_or_init<'a>(
+        loader: &'a AccountLoader<'_, Self>,
+        ai_character: Pubkey,
+        bump: u8,
+    ) -> Result<RefMut<'a, Self>> {
+        if let Ok(history) = loader.load_mut() {
+            return Ok(history);
+        }
+        let mut history = loader.load_init()?;
+        history.ai_character = ai_character;
+        history.bump = [bump];
+        Ok(history)
+    }
+
+    pub fn push(&mut self, version: u64, config: &CharacterConfig) {
+        let slot = (self.snapshot_count % CONFIG_HISTORY_LEN as u64) as usize;
+        self.snapshots[slot].version = version;
+        self.snapshots[slot].config = *config;
+        self.snapshot_count += 1;
+    }
+
+    /// Returns the slot holding the snapshot of `version`, if it is still kept
+    pub fn find(&self, version: u64) -> Option<usize> {
+        let stored = self.snapshot_count.min(CONFIG_HISTORY_LEN as u64) as usize;
+        self.snapshots[..stored]
+            .iter()
+            .position(|snapshot| { snapshot.version } == version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+    use crate::state::AiCharacterNFT;
+
+    #[test]
+    fn test_history_fits_in_single_allocation() {
+        // accounts created through `init` can not exceed the CPI allocation limit
+        assert!(8 + std::mem::size_of::<CharacterConfigHistory>() <= MAX_PERMITTED_DATA_INCREASE);
+    }
+
+    #[test]
+    fn test_history_keeps_latest_snapshots() {
+        let mut history = CharacterConfigHistory::zeroed();
+        let config = CharacterConfig::default();
+        for version in 0..(CONFIG_HISTORY_LEN as u64 + 1) {
+            history.push(version, &config);
+        }
+        assert_eq!(history.find(0), None);
+        assert_eq!(history.find(1), Some(1));
+        assert_eq!(history.find(CONFIG_HISTORY_LEN as u64), Some(0));
+    }
+
+    #[test]
+    fn test_rollback_restores_config() {
+        let mut history = CharacterConfigHistory::zeroed();
+        let mut ai_character = AiCharacterNFT::zeroed();
+        ai_character.character_config = CharacterConfig::default();
+        let original_hash = ai_character.character_config.hash();
+
+        ai_character.checkpoint_config(&mut history).unwrap();
+        ai_character.character_config.update_model_provider("openai".to_string());
+        assert_eq!({ ai_character.config_version }, 1);
+        assert_eq!(ai_character.previous_config_hash, original_hash);
+
+        ai_character.rollback_config(&mut history, 0).unwrap();
+        assert_eq!({ ai_character.config_version }, 2);
+        assert_eq!(ai_character.character_config.hash(), original_hash);
+        // the rolled back config can be restored again
+        assert!(history.find(1).is_some());
+        assert!(ai_character.rollback_config(&mut history, 0).is_err());
+    }
+
+    #[test]
+    fn test_rollback_keeps_current_name() {
+        let mut history = CharacterConfigHistory::zeroed();
+        let mut ai_character = AiCharacterNFT::zeroed();
+
+        ai_character.checkpoint_config(&mut history).unwrap();
+        ai_character.rename("Renamed".to_string()).unwrap();
+
+        ai_character.rollback_config(&mut history, 0).unwrap();
+        assert_eq!(&ai_character.character_config.name[..8], b"Renamed\0");
+    }
+
+    #[test]
+    fn test_frozen_config_rejects_changes() {
+        let mut history = CharacterConfigHistory::zeroed();
+        let mut ai_character = AiCharacterNFT::zeroed();
+        ai_character.checkpoint_config(&mut history).unwrap();
+
+        ai_character.freeze_config().unwrap();
+        assert_eq!(
+            ai_character.frozen_config_hash,
+            ai_character.character_config.hash()
+        );
+        assert!(ai_character.freeze_config().is_err());
+        assert!(ai_character.checkpoint_config(&mut history).is_err());
+        assert!(ai_character.rollback_config(&mut history, 0).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::MAX_NAME_LENGTH;
+
+use crate::error::AiNftError;
+
+// Registry entry claiming a character name within a collection,
+// seeded by ["character_name", ai_nft, name]
+#[account]
+pub struct CharacterName {
+    pub ai_nft: Pubkey,       // Collection the name is claimed in
+    pub ai_character: Pubkey, // Ai character holding the name
+    pub bump: u8,
+}
+
+impl CharacterName {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+
+    pub fn claim(&mut self, ai_nft: Pubkey, ai_character: Pubkey, bump: u8) {
+        self.ai_nft = ai_nft;
+        self.ai_character = ai_character;
+        self.bump = bump;
+    }
+
+    /// Checks a new character name. Names are stored zero padded, so they can't
+    /// contain zero bytes or the stored name would seed a different entry.
+    pub fn validate(name: &str) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_NAME_LENGTH && !name.contains('\0'),
+            AiNftError::InvalidCharacterName
+        );
+        Ok(())
+    }
+
+    /// Seed of a zero padded character name
+    pub fn seed(name: &[u8; 32]) -> &[u8] {
+        let len = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+        &name[..len]
+    }
+
+    /// Closes the registry entry of `name` held by `ai_character`.
+    /// Characters minted before the registry existed have no entry.
+    pub fn release<'info>(
+        entry: &AccountInfo<'info>,
+        ai_nft: &Pubkey,
+        ai_character: &Pubkey,
+        name: &[u8; 32],
+        destination: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let (address, _) = Pubkey::find_program_address(
+            &[
+                "character_name".as_bytes(),
+                ai_nft.as_ref(),
+                Self::seed(name),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(entry.key(), address, AiNftError::InvalidCharacterName);
+        if entry.owner != &crate::ID || entry.data_is_empty() {
+            return Ok(());
+        }
+        let character_name = Self::try_deserialize(&mut &entry.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            character_name.ai_character,
+            *ai_character,
+            AiNftError::InvalidCharacterName
+        );
+
+        let lamports = destination
+            .lamports()
+            .checked_add(entry.lamports())
+            .ok_or(AiNftError::Overflow)?;
+        **destination.try_borrow_mut_lamports()? = lamports;
+        **entry.try_borrow_mut_lamports()? = 0;
+        entry.assign(&System::id());
+        entry.realloc(0, false)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(CharacterName::validate("Agent").is_ok());
+        assert!(CharacterName::validate("").is_err());
+        assert!(CharacterName::validate(&"x".repeat(MAX_NAME_LENGTH + 1)).is_err());
+        assert!(CharacterName::validate("Age\0nt").is_err());
+    }
+
+    #[test]
+    fn test_seed_trims_padding() {
+        let mut name = [0u8; 32];
+        name[..5].copy_from_slice(b"Agent");
+        assert_eq!(CharacterName::seed(&name), b"Agent");
+        assert_eq!(CharacterName::seed(&[1u8; 32]), &[1u8; 32]);
+    }
+}
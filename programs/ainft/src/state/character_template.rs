@@ -0,0 +1,56 @@
+use std::cell::RefMut;
+
+use anchor_lang::{prelude::*, Discriminator};
+
+use super::{CharacterConfig, CharacterConfigInput};
+
+// Character template holds the config that newly minted
+// AI characters of a collection start from
+#[account(zero_copy(unsafe))]
+pub struct CharacterTemplate {
+    pub ai_nft: Pubkey, // 32 bytes
+    pub character_config: CharacterConfig,
+    pub bump: [u8; 1], // 1 byte
+}
+
+impl CharacterTemplate {
+    /// Loads the template for writing. The account is created with `init_if_needed`,
+    /// so it is initialized here the first time it is used.
+    pub fn load_or_init<'a>(
+        loader: &'a AccountLoader<'_, Self>,
+        ai_nft: Pubkey,
+        bump: u8,
+    ) -> Result<RefMut<'a, Self>> {
+        if let Ok(template) = loader.load_mut() {
+            return Ok(template);
+        }
+        let mut template = loader.load_init()?;
+        template.ai_nft = ai_nft;
+        template.bump = [bump];
+        Ok(template)
+    }
+
+    /// Config of the template behind the template PDA, if the collection has one
+    pub fn load_config(template: &AccountInfo) -> Result<Option<CharacterConfig>> {
+        if template.owner != &crate::ID || template.data_is_empty() {
+            return Ok(None);
+        }
+        let data = template.try_borrow_data()?;
+        let len = 8 + std::mem::size_of::<Self>();
+        require!(
+            data.len() >= len && data[..8] == Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let template: &Self = bytemuck::from_bytes(&data[8..len]);
+        Ok(Some(template.character_config))
+    }
+
+    pub fn update_character_config(
+        &mut self,
+        character_config: CharacterConfigInput,
+    ) -> Result<()> {
+        character_config.validate()?;
+        self.character_config = CharacterConfig::from_input(character_config);
+        Ok(())
+    }
+}
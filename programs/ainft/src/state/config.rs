@@ -1,11 +1,133 @@
 use anchor_lang::prelude::*;
 
+use crate::error::AiNftError;
+
+// Metaplex allows at most 5 creators, one is kept for the minter
+pub const MAX_ROYALTY_CREATORS: usize = 4;
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+pub const MAX_TREASURY_RECIPIENTS: usize = 5;
+pub const TOTAL_SHARE_BPS: u16 = 10_000;
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct RoyaltyCreator {
+    pub address: Pubkey,
+    pub share: u8, // Percentage of the royalties
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default, PartialEq)]
+pub struct RoyaltyConfig {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<RoyaltyCreator>,
+    pub minter_share: u8, // Share of the minter as a verified creator (0 to leave the minter out)
+}
+
+impl RoyaltyConfig {
+    pub const LEN: usize = 2 + 4 + MAX_ROYALTY_CREATORS * (32 + 1) + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.seller_fee_basis_points <= MAX_SELLER_FEE_BASIS_POINTS,
+            AiNftError::InvalidRoyalties
+        );
+        require!(
+            self.creators.len() <= MAX_ROYALTY_CREATORS,
+            AiNftError::InvalidRoyalties
+        );
+        for (i, creator) in self.creators.iter().enumerate() {
+            require!(
+                !self.creators[..i]
+                    .iter()
+                    .any(|other| other.address == creator.address),
+                AiNftError::InvalidRoyalties
+            );
+        }
+
+        // Shares must add up to 100 when there are any creators
+        let total_share = self
+            .creators
+            .iter()
+            .map(|creator| creator.share as u32)
+            .sum::<u32>()
+            + self.minter_share as u32;
+        require!(
+            total_share == 0 || total_share == 100,
+            AiNftError::InvalidRoyalties
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct TreasuryRecipient {
+    pub address: Pubkey,
+    pub share_bps: u16, // Share of the withdrawn mint proceeds in basis points
+}
+
+impl TreasuryRecipient {
+    pub const LEN: usize = 32 + 2;
+
+    /// Validates a treasury recipient list. An empty list sends the proceeds
+    /// to the collection authority, otherwise the shares add up to 100%.
+    pub fn validate_list(recipients: &[TreasuryRecipient]) -> Result<()> {
+        if recipients.is_empty() {
+            return Ok(());
+        }
+        require!(
+            recipients.len() <= MAX_TREASURY_RECIPIENTS,
+            AiNftError::InvalidTreasuryRecipients
+        );
+        for (i, recipient) in recipients.iter().enumerate() {
+            require!(
+                recipient.share_bps > 0
+                    && !recipients[..i]
+                        .iter()
+                        .any(|other| other.address == recipient.address),
+                AiNftError::InvalidTreasuryRecipients
+            );
+        }
+        let total_share = recipients
+            .iter()
+            .map(|recipient| recipient.share_bps as u32)
+            .sum::<u32>();
+        require!(
+            total_share == TOTAL_SHARE_BPS as u32,
+            AiNftError::InvalidTreasuryRecipients
+        );
+        Ok(())
+    }
+}
+
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct CreateAiNftParams {
+    pub collection_id: u64, // Distinguishes the collections of one creator
     pub name: String,
     pub uri: String,
     pub symbol: String,
     pub default_execution_client: Pubkey,
     pub mint_price: u64,
+    pub payment_mint: Option<Pubkey>, // Token the mint price is paid in, SOL if not set
     pub max_supply: u64,
+    pub royalties: RoyaltyConfig, // Royalties applied to every minted character
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default)]
+pub struct UpdateAiNftParams {
+    pub mint_price: Option<u64>,
+    pub max_supply: Option<u64>,
+    pub default_execution_client: Option<Pubkey>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default)]
+pub struct UpdateProtocolRolesParams {
+    pub admin: Option<Pubkey>,
+    pub fee_manager: Option<Pubkey>,
+    pub pauser: Option<Pubkey>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default)]
+pub struct UpdateProtocolLimitsParams {
+    pub max_content_length: Option<u32>,
+    pub max_response_length: Option<u32>,
+    pub max_gas: Option<u64>,
 }
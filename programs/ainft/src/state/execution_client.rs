@@ -2,6 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::error::AiNftError;
 
+use super::TOTAL_SHARE_BPS;
+
+/// Maximum number of compute mints an execution client accepts besides the collection compute mint
+pub const MAX_ACCEPTED_COMPUTE_MINTS: usize = 4;
 /// Number of decimals for gas representation
 pub const GAS_DECIMALS: u8 = 9;
 /// Multiplier for converting between gas units
@@ -25,6 +29,7 @@ pub struct ExecutionClient {
     pub active: bool, // Whether client is active
     pub bump: [u8; 1],
     pub supported_message_types: Vec<String>, // The message types that the execution client supports
+    pub accepted_compute_mints: [Pubkey; MAX_ACCEPTED_COMPUTE_MINTS], // Character compute mints the client accepts (default for unused slots)
 }
 
 // implement default for execution client
@@ -45,6 +50,7 @@ impl Default for ExecutionClient {
             active: false,
             bump: [22],
             supported_message_types: Vec::new(),
+            accepted_compute_mints: [Pubkey::default(); MAX_ACCEPTED_COMPUTE_MINTS],
         }
     }
 }
@@ -62,13 +68,11 @@ impl ExecutionClient {
         stake_pool_token_account: Pubkey,
         compute_token_address: Pubkey,
         staker_fee_share: u8,
+        max_gas: u64,
     ) -> Result<()> {
         require!(staker_fee_share <= 100, AiNftError::InvalidFeeShare);
-        // Ensure gas amount doesn't exceed total supply
-        require!(
-            gas <= 600_000_000 * GAS_DECIMALS_MULTIPLIER,
-            AiNftError::InvalidGasAmount
-        );
+        // Ensure gas amount doesn't exceed the protocol gas cap
+        require!(gas <= max_gas, AiNftError::InvalidGasAmount);
 
         self.ai_nft = ai_nft;
         self.authority = authority;
@@ -84,6 +88,7 @@ impl ExecutionClient {
         self.active = true;
         self.bump = [bump];
         self.supported_message_types = supported_message_types;
+        self.accepted_compute_mints = [Pubkey::default(); MAX_ACCEPTED_COMPUTE_MINTS];
         Ok(())
     }
 
@@ -101,11 +106,8 @@ impl ExecutionClient {
 
     /// Update gas amount (in smallest units)
     /// Example: To set gas to 2.2 compute tokens, pass 2_200_000_000
-    pub fn update_gas(&mut self, new_gas: u64) -> Result<()> {
-        require!(
-            new_gas <= 600_000_000 * GAS_DECIMALS_MULTIPLIER,
-            AiNftError::InvalidGasAmount
-        );
+    pub fn update_gas(&mut self, new_gas: u64, max_gas: u64) -> Result<()> {
+        require!(new_gas <= max_gas, AiNftError::InvalidGasAmount);
         self.gas = new_gas;
         Ok(())
     }
@@ -162,16 +164,44 @@ impl ExecutionClient {
         (staked_amount * self.total_compute) / self.total_staked
     }
 
+    /// Whether the client settles responses paid in `compute_mint`
+    pub fn accepts_compute_mint(&self, compute_mint: &Pubkey) -> bool {
+        *compute_mint == self.compute_mint
+            || (*compute_mint != Pubkey::default()
+                && self.accepted_compute_mints.contains(compute_mint))
+    }
+
+    pub fn set_accepted_compute_mints(&mut self, compute_mints: Vec<Pubkey>) -> Result<()> {
+        require!(
+            compute_mints.len() <= MAX_ACCEPTED_COMPUTE_MINTS,
+            AiNftError::InvalidAcceptedComputeMints
+        );
+        self.accepted_compute_mints = [Pubkey::default(); MAX_ACCEPTED_COMPUTE_MINTS];
+        self.accepted_compute_mints[..compute_mints.len()].copy_from_slice(&compute_mints);
+        Ok(())
+    }
+
     pub fn calculate_fee_split(&self) -> (u64, u64) {
-        let staker_portion = (self.gas as u128 * self.staker_fee_share as u128 / 100) as u64;
-        let client_portion = self.gas - staker_portion;
+        let (_, staker_portion, client_portion) = self.calculate_fee_split_with_protocol_fee(0);
         (staker_portion, client_portion)
     }
+
+    /// Splits the gas into the protocol fee, the staker and the client portions.
+    /// The protocol fee is taken first, stakers get their share of the rest.
+    pub fn calculate_fee_split_with_protocol_fee(&self, protocol_fee_bps: u16) -> (u64, u64, u64) {
+        let protocol_portion =
+            (self.gas as u128 * protocol_fee_bps as u128 / TOTAL_SHARE_BPS as u128) as u64;
+        let remaining = self.gas - protocol_portion;
+        let staker_portion = (remaining as u128 * self.staker_fee_share as u128 / 100) as u64;
+        let client_portion = remaining - staker_portion;
+        (protocol_portion, staker_portion, client_portion)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::DEFAULT_MAX_GAS;
 
     /// test fee split
     #[test]
@@ -209,6 +239,20 @@ mod tests {
         assert_eq!(fee_split.1, 100 * GAS_DECIMALS_MULTIPLIER);
     }
 
+    #[test]
+    fn test_fee_split_with_protocol_fee() {
+        let client = ExecutionClient {
+            staker_fee_share: 50,
+            gas: 100 * GAS_DECIMALS_MULTIPLIER,
+            ..Default::default()
+        };
+        let (protocol, staker, client_portion) = client.calculate_fee_split_with_protocol_fee(1_000);
+        assert_eq!(protocol, 10 * GAS_DECIMALS_MULTIPLIER);
+        assert_eq!(staker, 45 * GAS_DECIMALS_MULTIPLIER);
+        assert_eq!(client_portion, 45 * GAS_DECIMALS_MULTIPLIER);
+        assert_eq!(protocol + staker + client_portion, client.gas);
+    }
+
     #[test]
     fn test_gas_handling() {
         let mut client = ExecutionClient {
@@ -226,19 +270,20 @@ mod tests {
             active: true,
             bump: [0],
             supported_message_types: vec![],
+            accepted_compute_mints: [Pubkey::default(); MAX_ACCEPTED_COMPUTE_MINTS],
         };
 
         // Test setting gas to 2.2 compute tokens (2_200_000_000 in smallest units)
-        client.update_gas(2_200_000_000).unwrap();
+        client.update_gas(2_200_000_000, DEFAULT_MAX_GAS).unwrap();
         assert_eq!(client.get_gas(), 2_200_000_000);
 
         // Test setting gas to 100 compute tokens
-        client.update_gas(100_000_000_000).unwrap();
+        client.update_gas(100_000_000_000, DEFAULT_MAX_GAS).unwrap();
         assert_eq!(client.get_gas(), 100_000_000_000);
 
         // Test setting maximum allowed gas (600M tokens)
         client
-            .update_gas(600_000_000 * GAS_DECIMALS_MULTIPLIER)
+            .update_gas(600_000_000 * GAS_DECIMALS_MULTIPLIER, DEFAULT_MAX_GAS)
             .unwrap();
         assert_eq!(client.get_gas(), 600_000_000 * GAS_DECIMALS_MULTIPLIER);
     }
@@ -261,11 +306,12 @@ mod tests {
             active: true,
             bump: [0],
             supported_message_types: vec![],
+            accepted_compute_mints: [Pubkey::default(); MAX_ACCEPTED_COMPUTE_MINTS],
         };
 
         // This should fail as it exceeds max supply
         client
-            .update_gas(601_000_000 * GAS_DECIMALS_MULTIPLIER)
+            .update_gas(601_000_000 * GAS_DECIMALS_MULTIPLIER, DEFAULT_MAX_GAS)
             .unwrap();
     }
 
@@ -286,6 +332,7 @@ mod tests {
             active: true,
             bump: [0],
             supported_message_types: vec![],
+            accepted_compute_mints: [Pubkey::default(); MAX_ACCEPTED_COMPUTE_MINTS],
         };
 
         // Should return 2.0 in smallest units (2_000_000_000)
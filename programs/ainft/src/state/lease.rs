@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+// Lease of an ai character, seeded by ["lease", ai_character]. While the lease
+// is active the renter holds the rights of the nft holder.
+#[account]
+pub struct Lease {
+    pub ai_character: Pubkey,
+    pub lessor: Pubkey,   // Nft holder offering the lease, receives the rent
+    pub renter: Pubkey,   // Renter of the character (default until rented)
+    pub rent_price: u64,  // Rent in compute tokens, paid up front
+    pub duration: i64,    // Lease duration in seconds
+    pub expires_at: i64,  // End of the lease (0 until rented)
+    pub bump: u8,
+}
+
+impl Lease {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    pub fn is_rented(&self) -> bool {
+        self.renter != Pubkey::default()
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.is_rented() && now < self.expires_at
+    }
+
+    pub fn rent(&mut self, renter: Pubkey, now: i64) {
+        self.renter = renter;
+        self.expires_at = now.saturating_add(self.duration);
+    }
+
+    /// Loads the lease behind the lease PDA if it exists
+    pub fn load(lease: &AccountInfo) -> Option<Lease> {
+        if lease.owner != &crate::ID || lease.data_is_empty() {
+            return None;
+        }
+        Lease::try_deserialize(&mut &lease.try_borrow_data().ok()?[..]).ok()
+    }
+
+    /// Loads the lease behind the lease PDA if it exists and is active
+    pub fn load_active(lease: &AccountInfo) -> Option<Lease> {
+        let lease = Lease::load(lease)?;
+        let now = Clock::get().ok()?.unix_timestamp;
+        lease.is_active(now).then_some(lease)
+    }
+
+    /// Whether the character is rented out. The revenue of a rented character goes
+    /// to the renter until the lease is closed, even once it expired.
+    pub fn is_rented_out(lease: &AccountInfo) -> bool {
+        Lease::load(lease).is_some_and(|lease| lease.is_rented())
+    }
+
+    /// Whether `authority` holds the rights of the nft holder: the renter of an
+    /// active lease, or otherwise the holder of the nft in `token_account`
+    pub fn is_character_authority(
+        lease: &AccountInfo,
+        token_account: &TokenAccount,
+        authority: &Pubkey,
+    ) -> bool {
+        match Lease::load_active(lease) {
+            Some(lease) => lease.renter == *authority,
+            None => token_account.owner == *authority && token_account.amount == 1,
+        }
+    }
+
+    /// Receiver of the character revenue: the renter of an active lease, or
+    /// otherwise the nft holder
+    pub fn revenue_recipient(lease: &AccountInfo, holder: Pubkey) -> Pubkey {
+        Lease::load_active(lease).map_or(holder, |lease| lease.renter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lease_expiry() {
+        let mut lease = Lease {
+            ai_character: Pubkey::new_unique(),
+            lessor: Pubkey::new_unique(),
+            renter: Pubkey::default(),
+            rent_price: 10,
+            duration: 100,
+            expires_at: 0,
+            bump: 0,
+        };
+        assert!(!lease.is_active(0));
+
+        lease.rent(Pubkey::new_unique(), 1_000);
+        assert!(lease.is_rented());
+        assert!(lease.is_active(1_099));
+        assert!(!lease.is_active(1_100));
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use super::TOTAL_SHARE_BPS;
+
+// Marketplace listing of an ai character, seeded by ["listing", ai_character_mint].
+// The listing PDA holds the agent nft in escrow until it is sold or cancelled.
+#[account]
+pub struct Listing {
+    pub seller: Pubkey,
+    pub ai_character: Pubkey,
+    pub ai_character_mint: Pubkey,
+    pub price: u64,           // Price in lamports, or in compute tokens
+    pub payment_mint: Pubkey, // Compute mint the price is paid in (default for SOL)
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Listing {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 32 + 8 + 1;
+
+    pub fn is_token_payment(&self) -> bool {
+        self.payment_mint != Pubkey::default()
+    }
+
+    pub fn as_seeds(&self) -> [&[u8]; 3] {
+        [
+            "listing".as_bytes(),
+            self.ai_character_mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    /// Splits the sale price into the royalty of each creator and the amount
+    /// left for the seller. Creator shares are percentages adding up to 100.
+    pub fn split_sale(
+        &self,
+        seller_fee_basis_points: u16,
+        creators: &[(Pubkey, u8)],
+    ) -> (Vec<(Pubkey, u64)>, u64) {
+        let royalty = if creators.is_empty() {
+            0
+        } else {
+            (self.price as u128 * seller_fee_basis_points as u128 / TOTAL_SHARE_BPS as u128) as u64
+        };
+        let royalties: Vec<(Pubkey, u64)> = creators
+            .iter()
+            .map(|(address, share)| (*address, (royalty as u128 * *share as u128 / 100) as u64))
+            .collect();
+        let paid_royalties: u64 = royalties.iter().map(|(_, amount)| amount).sum();
+        (royalties, self.price - paid_royalties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sale() {
+        let listing = Listing {
+            seller: Pubkey::new_unique(),
+            ai_character: Pubkey::new_unique(),
+            ai_character_mint: Pubkey::new_unique(),
+            price: 1_000,
+            payment_mint: Pubkey::default(),
+            created_at: 0,
+            bump: 0,
+        };
+        let creators = [(Pubkey::new_unique(), 70), (Pubkey::new_unique(), 30)];
+
+        // 5% royalties split 70/30 between the creators
+        let (royalties, seller_amount) = listing.split_sale(500, &creators);
+        assert_eq!(royalties[0].1, 35);
+        assert_eq!(royalties[1].1, 15);
+        assert_eq!(seller_amount, 950);
+
+        let (royalties, seller_amount) = listing.split_sale(500, &[]);
+        assert!(royalties.is_empty());
+        assert_eq!(seller_amount, 1_000);
+    }
+}
@@ -12,6 +12,7 @@ pub struct MessageAiCharacter {
     pub answered: bool,           // 1 byte
     pub created_at: i64,          // 8 bytes
     pub updated_at: i64,          // 8 bytes
+    pub owner_markup: u64,        // 8 bytes, owner markup paid by the sender
     pub bump: u8,                 // 1 byte
 }
 
@@ -21,6 +22,7 @@ pub mod message_constants {
     pub const BOOL_SIZE: usize = 1;
     pub const U8_SIZE: usize = 1;
     pub const I64_SIZE: usize = 8;
+    pub const U64_SIZE: usize = 8;
     pub const STRING_PREFIX_SIZE: usize = 4; // Size of the length prefix for strings
     pub const OPTION_PREFIX_SIZE: usize = 1; // Size of the discriminator for Option
 
@@ -36,6 +38,7 @@ pub mod message_constants {
         BOOL_SIZE +                  // answered
         I64_SIZE +                   // created_at
         I64_SIZE +                   // updated_at
+        U64_SIZE +                   // owner_markup
         U8_SIZE; // bump
 }
 
@@ -45,6 +48,7 @@ impl MessageAiCharacter {
         ai_character: &Pubkey,
         sender: &Pubkey,
         content: &str,
+        owner_markup: u64,
         bump: u8,
     ) -> Self {
         let current_timestamp = Clock::get().unwrap().unix_timestamp;
@@ -57,13 +61,14 @@ impl MessageAiCharacter {
             answered: false,
             created_at: current_timestamp,
             updated_at: current_timestamp,
+            owner_markup,
             bump,
         }
     }
 
-    pub fn answer(&mut self, response: &str) {
-        let response_str = if response.len() > message_constants::MAX_RESPONSE_LENGTH {
-            &response[0..message_constants::MAX_RESPONSE_LENGTH]
+    pub fn answer(&mut self, response: &str, max_response_length: usize) {
+        let response_str = if response.len() > max_response_length {
+            &response[0..max_response_length]
         } else {
             response
         };
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::error::AiNftError;
+
+pub const MAX_MINT_PHASES: usize = 4;
+
+// A mint phase of a collection, e.g. an allowlist phase followed by a public phase
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default, PartialEq)]
+pub struct MintPhase {
+    pub start_time: i64,
+    pub end_time: i64,          // 0 for no end
+    pub price: u64,             // Price in lamports, or in payment mint tokens
+    pub max_per_wallet: u32,    // 0 for unlimited
+    pub merkle_root: [u8; 32],  // Allowlist root, zero for a public phase
+}
+
+impl MintPhase {
+    pub const LEN: usize = 8 + 8 + 8 + 4 + 32;
+
+    pub fn is_active(&self, now: i64) -> bool {
+        now >= self.start_time && (self.end_time == 0 || now < self.end_time)
+    }
+
+    pub fn is_allowlist(&self) -> bool {
+        self.merkle_root != [0; 32]
+    }
+
+    /// Verifies that `wallet` is part of the allowlist. Leaves are the keccak
+    /// hash of the wallet and pairs are hashed in sorted order.
+    pub fn verify_allowlist(&self, wallet: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        if !self.is_allowlist() {
+            return Ok(());
+        }
+        let mut node = keccak::hash(wallet.as_ref()).to_bytes();
+        for sibling in proof {
+            node = if node <= *sibling {
+                keccak::hashv(&[&node, sibling]).to_bytes()
+            } else {
+                keccak::hashv(&[sibling, &node]).to_bytes()
+            };
+        }
+        require!(node == self.merkle_root, AiNftError::NotAllowlisted);
+        Ok(())
+    }
+
+    /// Validates a phase list. Phases are sorted by start time and don't overlap.
+    pub fn validate_list(phases: &[MintPhase]) -> Result<()> {
+        require!(
+            phases.len() <= MAX_MINT_PHASES,
+            AiNftError::InvalidMintPhases
+        );
+        for (i, phase) in phases.iter().enumerate() {
+            require!(
+                phase.end_time == 0 || phase.start_time < phase.end_time,
+                AiNftError::InvalidMintPhases
+            );
+            if let Some(previous) = i.checked_sub(1).map(|i| &phases[i]) {
+                require!(
+                    previous.end_time != 0 && previous.end_time <= phase.start_time,
+                    AiNftError::InvalidMintPhases
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+// Number of characters a wallet minted in each phase of a collection
+#[account]
+pub struct WalletMintCount {
+    pub ai_nft: Pubkey,
+    pub wallet: Pubkey,
+    pub mint_phases_version: u32, // Version of the mint phases the counts belong to
+    pub phase_mint_counts: [u32; MAX_MINT_PHASES],
+    pub bump: u8,
+}
+
+impl WalletMintCount {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 4 * MAX_MINT_PHASES + 1;
+
+    /// Records a mint in the phase at `phase_index`. The counts are reset when
+    /// the collection replaced its mint phases since the last mint.
+    pub fn record_mint(
+        &mut self,
+        mint_phases_version: u32,
+        phase_index: usize,
+        phase: &MintPhase,
+    ) -> Result<()> {
+        if self.mint_phases_version != mint_phases_version {
+            self.mint_phases_version = mint_phases_version;
+            self.phase_mint_counts = [0; MAX_MINT_PHASES];
+        }
+        let count = &mut self.phase_mint_counts[phase_index];
+        require!(
+            phase.max_per_wallet == 0 || *count < phase.max_per_wallet,
+            AiNftError::WalletMintLimitReached
+        );
+        *count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[&a, &b]).to_bytes()
+        } else {
+            keccak::hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn test_verify_allowlist() {
+        let wallets: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = wallets
+            .iter()
+            .map(|wallet| keccak::hash(wallet.as_ref()).to_bytes())
+            .collect();
+        let left = hash_pair(leaves[0], leaves[1]);
+        let phase = MintPhase {
+            merkle_root: hash_pair(left, leaves[2]),
+            ..MintPhase::default()
+        };
+
+        assert!(phase.verify_allowlist(&wallets[0], &[leaves[1], leaves[2]]).is_ok());
+        assert!(phase.verify_allowlist(&wallets[2], &[left]).is_ok());
+        assert!(phase
+            .verify_allowlist(&Pubkey::new_unique(), &[leaves[1], leaves[2]])
+            .is_err());
+    }
+
+    #[test]
+    fn test_wallet_mint_limit() {
+        let phase = MintPhase {
+            max_per_wallet: 1,
+            ..MintPhase::default()
+        };
+        let mut wallet_mint_count = WalletMintCount {
+            ai_nft: Pubkey::default(),
+            wallet: Pubkey::default(),
+            mint_phases_version: 0,
+            phase_mint_counts: [0; MAX_MINT_PHASES],
+            bump: 0,
+        };
+        assert!(wallet_mint_count.record_mint(0, 0, &phase).is_ok());
+        assert!(wallet_mint_count.record_mint(0, 0, &phase).is_err());
+        assert!(wallet_mint_count.record_mint(0, 1, &phase).is_ok());
+        // replaced phases start from a zero count
+        assert!(wallet_mint_count.record_mint(1, 0, &phase).is_ok());
+        assert_eq!(wallet_mint_count.phase_mint_counts, [1, 0, 0, 0]);
+    }
+}
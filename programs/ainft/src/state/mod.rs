@@ -1,13 +1,27 @@
 pub mod ai_character;
 pub mod ai_nft;
+pub mod character_config_history;
+pub mod character_name;
+pub mod character_template;
 pub mod config;
 pub mod execution_client;
+pub mod lease;
+pub mod listing;
 pub mod message;
+pub mod mint_phase;
+pub mod protocol_config;
 pub mod stake_account;
 
 pub use ai_character::*;
 pub use ai_nft::*;
+pub use character_config_history::*;
+pub use character_name::*;
+pub use character_template::*;
 pub use config::*;
 pub use execution_client::*;
+pub use lease::*;
+pub use listing::*;
 pub use message::*;
+pub use mint_phase::*;
+pub use protocol_config::*;
 pub use stake_account::*;
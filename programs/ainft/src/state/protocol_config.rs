@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AiNftError;
+
+use super::{
+    message_constants::{MAX_CONTENT_LENGTH, MAX_RESPONSE_LENGTH},
+    UpdateProtocolLimitsParams, UpdateProtocolRolesParams, GAS_DECIMALS_MULTIPLIER,
+};
+
+/// Bit flags of the subsystems the pauser can pause
+pub mod pause_flags {
+    pub const MINTING: u8 = 1 << 0;
+    pub const MESSAGING: u8 = 1 << 1;
+    pub const RESPONSES: u8 = 1 << 2;
+    pub const STAKING: u8 = 1 << 3;
+    pub const CONFIG_UPDATES: u8 = 1 << 4;
+
+    pub const ALL: u8 = (1 << 5) - 1;
+}
+
+/// Gas cap of an execution client until the admin changes it
+pub const DEFAULT_MAX_GAS: u64 = 600_000_000 * GAS_DECIMALS_MULTIPLIER;
+/// Compute tokens charged per message, before the owner markup
+pub const DEFAULT_MESSAGE_FEE: u64 = 1;
+
+// Protocol wide limits and roles, a single PDA for the program
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,            // Sets the roles and the limits
+    pub fee_manager: Pubkey,      // Sets the message fee
+    pub pauser: Pubkey,           // Pauses the protocol in an emergency
+    pub max_content_length: u32,  // Max message length, up to MAX_CONTENT_LENGTH
+    pub max_response_length: u32, // Max response length, up to MAX_RESPONSE_LENGTH
+    pub max_gas: u64,             // Max gas an execution client can charge
+    pub message_fee: u64,         // Compute tokens charged per message
+    pub paused: u8,               // Paused subsystems, see `pause_flags`
+    pub withdraw_only: bool,      // Pauses everything except unstaking
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 4 + 4 + 8 + 8 + 1 + 1 + 1;
+
+    pub fn new(admin: Pubkey, bump: u8) -> Self {
+        Self {
+            admin,
+            fee_manager: admin,
+            pauser: admin,
+            max_content_length: MAX_CONTENT_LENGTH as u32,
+            max_response_length: MAX_RESPONSE_LENGTH as u32,
+            max_gas: DEFAULT_MAX_GAS,
+            message_fee: DEFAULT_MESSAGE_FEE,
+            paused: 0,
+            withdraw_only: false,
+            bump,
+        }
+    }
+
+    pub fn set_roles(&mut self, params: UpdateProtocolRolesParams) {
+        if let Some(admin) = params.admin {
+            self.admin = admin;
+        }
+        if let Some(fee_manager) = params.fee_manager {
+            self.fee_manager = fee_manager;
+        }
+        if let Some(pauser) = params.pauser {
+            self.pauser = pauser;
+        }
+    }
+
+    /// Message accounts are allocated for the compile time maximum lengths,
+    /// so the configured limits can only go below them
+    pub fn set_limits(&mut self, params: UpdateProtocolLimitsParams) -> Result<()> {
+        if let Some(max_content_length) = params.max_content_length {
+            require!(
+                max_content_length > 0 && max_content_length as usize <= MAX_CONTENT_LENGTH,
+                AiNftError::InvalidProtocolLimits
+            );
+            self.max_content_length = max_content_length;
+        }
+        if let Some(max_response_length) = params.max_response_length {
+            require!(
+                max_response_length > 0 && max_response_length as usize <= MAX_RESPONSE_LENGTH,
+                AiNftError::InvalidProtocolLimits
+            );
+            self.max_response_length = max_response_length;
+        }
+        if let Some(max_gas) = params.max_gas {
+            // a zero cap would leave no valid gas for any execution client
+            require!(max_gas > 0, AiNftError::InvalidProtocolLimits);
+            self.max_gas = max_gas;
+        }
+        Ok(())
+    }
+
+    pub fn set_message_fee(&mut self, message_fee: u64) {
+        self.message_fee = message_fee;
+    }
+
+    pub fn set_pause(&mut self, paused: u8, withdraw_only: bool) -> Result<()> {
+        require!(paused & !pause_flags::ALL == 0, AiNftError::InvalidPauseFlags);
+        self.paused = paused;
+        self.withdraw_only = withdraw_only;
+        Ok(())
+    }
+
+    /// Whether the subsystem in `flag` is paused, the withdraw only mode
+    /// pauses every subsystem
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.withdraw_only || self.paused & flag != 0
+    }
+
+    /// Unstaking stays open in the withdraw only mode so stakers can exit
+    pub fn can_unstake(&self) -> bool {
+        self.withdraw_only || !self.is_paused(pause_flags::STAKING)
+    }
+
+    pub fn check_content(&self, content: &str) -> Result<()> {
+        require!(
+            content.len() <= self.max_content_length as usize,
+            AiNftError::ContentTooLong
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_limits() {
+        let mut config = ProtocolConfig::new(Pubkey::new_unique(), 255);
+        config
+            .set_limits(UpdateProtocolLimitsParams {
+                max_content_length: Some(10),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(config.check_content("0123456789").is_ok());
+        assert!(config.check_content("0123456789a").is_err());
+        assert_eq!(config.max_response_length as usize, MAX_RESPONSE_LENGTH);
+
+        // limits can't exceed the allocated message size
+        assert!(config
+            .set_limits(UpdateProtocolLimitsParams {
+                max_response_length: Some(MAX_RESPONSE_LENGTH as u32 + 1),
+                ..Default::default()
+            })
+            .is_err());
+        assert!(config
+            .set_limits(UpdateProtocolLimitsParams {
+                max_gas: Some(0),
+                ..Default::default()
+            })
+            .is_err());
+        assert_eq!({ config.max_gas }, DEFAULT_MAX_GAS);
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut config = ProtocolConfig::new(Pubkey::new_unique(), 255);
+        config.set_pause(pause_flags::STAKING, false).unwrap();
+        assert!(config.is_paused(pause_flags::STAKING));
+        assert!(!config.is_paused(pause_flags::MINTING));
+        assert!(!config.can_unstake());
+
+        // withdraw only pauses everything but unstaking
+        config.set_pause(0, true).unwrap();
+        assert!(config.is_paused(pause_flags::MINTING));
+        assert!(config.is_paused(pause_flags::STAKING));
+        assert!(config.can_unstake());
+
+        assert!(config.set_pause(1 << 7, false).is_err());
+    }
+}
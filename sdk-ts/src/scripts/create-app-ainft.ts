@@ -1,5 +1,5 @@
 import { Keypair, PublicKey, sendAndConfirmTransaction, Transaction } from '@solana/web3.js';
-import { getProgram, findMasterMintPDA, findAppAinftPDA, findMetadataPDA } from '../utils';
+import { getProgram, findMasterMintPDA, findAppAinftPDA, findMetadataPDA, findMasterEditionPDA } from '../utils';
 import { Logger } from '../utils/logger';
 import { BN } from '@coral-xyz/anchor';
 import { associatedAddress } from '@coral-xyz/anchor/dist/cjs/utils/token';
@@ -30,8 +30,8 @@ async function main() {
         const program = await getProgram();
 
         Logger.startSpinner('Deriving program addresses...');
-        const [appAinftPda] = findAppAinftPDA();
-        const [masterMint] = findMasterMintPDA();
+        const [appAinftPda] = findAppAinftPDA(payer.publicKey);
+        const [masterMint] = findMasterMintPDA(appAinftPda);
         // get master_metadata account 
         const [masterMetadata] = findMetadataPDA(masterMint);
         // get token account for payer
@@ -44,12 +44,15 @@ async function main() {
         Logger.startSpinner('Creating AI NFT App...');
         const ix = await program.methods
             .createAppAinft({
+                collectionId: new BN(0),
                 name: 'AI NFT App',
                 uri: 'https://ai-nft-app.com',
                 symbol: 'AINFT',
                 defaultExecutionClient: PublicKey.default,
                 mintPrice: new BN(1),
+                paymentMint: null,
                 maxSupply: new BN(10),
+                royalties: { sellerFeeBasisPoints: 500, creators: [], minterShare: 100 },
             })
             .accounts({
                 // @ts-ignore
@@ -57,6 +60,7 @@ async function main() {
                 masterMint,
                 masterToken: tokenAccount,
                 masterMetadata: masterMetadata,
+                masterEdition: findMasterEditionPDA(masterMint)[0],
                 payer: payer.publicKey,
             })
             .signers([payer])
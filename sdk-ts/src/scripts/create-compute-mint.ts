@@ -22,9 +22,9 @@ async function main() {
         const program = await getProgram();
 
         Logger.startSpinner('Deriving program addresses...');
-        const [masterMint] = findMasterMintPDA();
-        const [appAinftPda] = findAppAinftPDA();
-        const [computeMint] = findComputeMintPDA();
+        const [appAinftPda] = findAppAinftPDA(payer.publicKey);
+        const [masterMint] = findMasterMintPDA(appAinftPda);
+        const [computeMint] = findComputeMintPDA(appAinftPda);
         const [computeMintMetadata] = findComputeMetadataPDA(computeMint);
         Logger.spinnerSuccess('Program addresses derived');
 
@@ -39,9 +39,9 @@ async function main() {
         const program = await getProgram();
 
         Logger.startSpinner('Deriving program addresses...');
-        const [masterMint] = findMasterMintPDA();
-        const [appAinftPda] = findAppAinftPDA();
-        const [computeMint] = findComputeMintPDA();
+        const [appAinftPda] = findAppAinftPDA(payer.publicKey);
+        const [masterMint] = findMasterMintPDA(appAinftPda);
+        const [computeMint] = findComputeMintPDA(appAinftPda);
         const recipientTokenAccount = await associatedAddress({ mint: computeMint, owner: new PublicKey(address) });
         Logger.spinnerSuccess('Program addresses derived');
 
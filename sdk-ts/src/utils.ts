@@ -56,17 +56,17 @@ export async function getProgram(): Promise<Program<Ainft>> {
     return new Program<Ainft>(IDL as Ainft, provider);
 }
 
-export function findMasterMintPDA(): [PublicKey, number] {
-    const [appAinftPda] = findAppAinftPDA();
+export function findMasterMintPDA(appAinft: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("master_mint"), appAinftPda.toBuffer()],
+        [Buffer.from("master_mint"), appAinft.toBuffer()],
         PROGRAM_ID
     );
 }
 
-export function findAppAinftPDA(): [PublicKey, number] {
+// collections are seeded by their creator and a per creator collection id
+export function findAppAinftPDA(creator: PublicKey, collectionId: BN = new BN(0)): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("app_ainft")],
+        [Buffer.from("app_ainft"), creator.toBuffer(), collectionId.toArrayLike(Buffer, "le", 8)],
         PROGRAM_ID
     );
 }
@@ -80,10 +80,9 @@ export function findLiquidStakingTokenMintPDA(appAinft: PublicKey, executionClie
 }
 
 
-export function findComputeMintPDA(): [PublicKey, number] {
-    const [appAinftPda] = findAppAinftPDA();
+export function findComputeMintPDA(appAinft: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("compute_mint"), appAinftPda.toBuffer()],
+        [Buffer.from("compute_mint"), appAinft.toBuffer()],
         PROGRAM_ID
     );
 }
@@ -95,6 +94,13 @@ export function findMetadataPDA(masterMint: PublicKey): [PublicKey, number] {
     );
 }
 
+export function findMasterEditionPDA(mint: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from("edition")],
+        METADATA_PROGRAM_ID
+    );
+}
+
 export function findComputeMetadataPDA(computeMint: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
         [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), computeMint.toBuffer()],
@@ -113,10 +119,10 @@ export function findAiCharacterPDA(
 
 export function findAiCharacterMintPDA(
     aiNft: PublicKey,
-    name: string
+    mintIndex: BN
 ): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("mint"), aiNft.toBuffer(), Buffer.from(name)],
+        [Buffer.from("mint"), aiNft.toBuffer(), mintIndex.toArrayLike(Buffer, "le", 8)],
         PROGRAM_ID
     );
 }
@@ -166,9 +172,9 @@ export function findMessagePDA(
 }
 
 export function findAiCharacterComputeTokenAccount(
-    aiCharacter: PublicKey
+    aiCharacter: PublicKey,
+    computeMint: PublicKey
 ): PublicKey {
-    const [computeMint] = findComputeMintPDA();
     return associatedAddress({
         mint: computeMint,
         owner: aiCharacter
@@ -5,7 +5,7 @@ import { LAMPORTS_PER_SOL, PublicKey, Signer, SystemProgram, SYSVAR_RENT_PUBKEY,
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import * as spl from "@solana/spl-token"
 import { assert } from "chai";
-import { findMasterMintPDA, findAppAinftPDA, findComputeMintPDA, findMetadataPDA, findAiCharacterMintPDA, findAiCharacterPDA } from "../sdk-ts/src/utils";
+import { findMasterMintPDA, findAppAinftPDA, findComputeMintPDA, findMetadataPDA, findMasterEditionPDA, findAiCharacterMintPDA, findAiCharacterPDA } from "../sdk-ts/src/utils";
 
 const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
@@ -32,11 +32,12 @@ describe("ainft", async () => {
 
   const program = anchor.workspace.Ainft as Program<Ainft>;
 
-  let [appAinftPda] = findAppAinftPDA();
-  let [masterMint] = findMasterMintPDA();
-  let [computeMint] = findComputeMintPDA();
-  let [masterMetadata] = findMetadataPDA(masterMint);
   var payer = anchor.web3.Keypair.generate();
+  let [appAinftPda] = findAppAinftPDA(payer.publicKey);
+  let [masterMint] = findMasterMintPDA(appAinftPda);
+  let [computeMint] = findComputeMintPDA(appAinftPda);
+  let [masterMetadata] = findMetadataPDA(masterMint);
+  let [masterEdition] = findMasterEditionPDA(masterMint);
   var masterToken = await anchor.utils.token.associatedAddress({
     mint: masterMint,
     owner: payer.publicKey
@@ -46,6 +47,28 @@ describe("ainft", async () => {
   let stakerComputeAccount: PublicKey;
   const mintAmount = 200_000_000_000;
 
+  const [protocolConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_config")],
+    program.programId
+  );
+
+  before(async function () {
+    // The provider wallet deployed the program, so it is the upgrade authority
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializeProtocolConfig()
+      .accounts({
+        protocolConfig,
+        program: program.programId,
+        programData,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+
   beforeEach(async function () {
     // Setup payer and airdrop
     const signature = await provider.connection.requestAirdrop(
@@ -57,12 +80,15 @@ describe("ainft", async () => {
     // Create the AI NFT collection
     const defaultExecutionClient = anchor.web3.Keypair.generate().publicKey;
     const createAiNftParams = {
+      collectionId: new BN(0),
       name: "AI Agent Collection",
       uri: "https://example.com/metadata.json",
       symbol: "AIA",
       defaultExecutionClient: defaultExecutionClient,
       mintPrice: new BN(100),
+      paymentMint: null,
       maxSupply: new BN(100),
+      royalties: { sellerFeeBasisPoints: 500, creators: [], minterShare: 100 },
       computeMint: PublicKey.default, // Set to default for external mint
     };
     console.log("defaultExecutionClient", defaultExecutionClient.toBase58());
@@ -73,6 +99,7 @@ describe("ainft", async () => {
         masterMint: masterMint,
         masterToken: masterToken,
         masterMetadata: masterMetadata,
+        masterEdition: masterEdition,
         payer: payer.publicKey,
       })
       .signers([payer])
@@ -179,7 +206,8 @@ describe("ainft", async () => {
       }
     };
 
-    const [aiCharacterMint] = findAiCharacterMintPDA(appAinftPda, aiCharacterName);
+    const { mintCount } = await program.account.aiNft.fetch(appAinftPda);
+    const [aiCharacterMint] = findAiCharacterMintPDA(appAinftPda, mintCount);
     const [aiCharacter] = findAiCharacterPDA(aiCharacterMint);
     const [aiCharacterMetadata] = findMetadataPDA(aiCharacterMint);
 
@@ -211,6 +239,7 @@ describe("ainft", async () => {
       .mintAinft(
         aiNftMetadata.name,
         aiNftMetadata.uri,
+        [], // allowlist proof
       )
       .accounts({
         payer: payer.publicKey,
@@ -218,6 +247,9 @@ describe("ainft", async () => {
         aiCharacter: aiCharacter,
         aiCharacterMint: aiCharacterMint,
         aiCharacterMetadata: aiCharacterMetadata,
+        masterMint: masterMint,
+        masterMetadata: masterMetadata,
+        masterEdition: masterEdition,
         payerAiCharacterTokenAccount: payerAiCharacterTokenAccount,
       })
       .signers([payer])
@@ -281,19 +313,6 @@ describe("ainft", async () => {
     // Test updating character config fields individually
     console.log("Testing character config field updates");
 
-    // Update name
-    await program.methods
-      .updateCharacterName("Updated Assistant")
-      .accounts({
-        aiNft: appAinftPda,
-        aiCharacter: aiCharacter,
-        authority: payer.publicKey,
-        aiCharacterMint: aiCharacterMint,
-        authorityAiCharacterTokenAccount: payerAiCharacterTokenAccount
-      })
-      .signers([payer])
-      .rpc();
-
     // Update clients
     console.log("Updating clients");
     await program.methods
@@ -492,7 +511,7 @@ describe("ainft", async () => {
     const updatedModelProvider = String.fromCharCode(...updatedAiCharacter.characterConfig.modelProvider.filter(b => b !== 0));
 
     // Verify updates
-    assert.equal(updatedName, "Updated Assistant", "Name should be updated");
+    assert.equal(updatedName, aiCharacterName, "Name should only change through renameCharacter");
     assert.equal(updatedClients[0], "default", "First client should be 'default'");
     assert.equal(updatedModelProvider, "anthropic", "Model provider should be updated");
 
@@ -539,7 +558,8 @@ describe("ainft", async () => {
       }
     };
 
-    const [aiCharacterMint] = findAiCharacterMintPDA(appAinftPda, aiNftMetadata.name);
+    const { mintCount } = await program.account.aiNft.fetch(appAinftPda);
+    const [aiCharacterMint] = findAiCharacterMintPDA(appAinftPda, mintCount);
     const [aiCharacter] = findAiCharacterPDA(aiCharacterMint);
     const [aiCharacterMetadata] = findMetadataPDA(aiCharacterMint);
 
@@ -561,13 +581,16 @@ describe("ainft", async () => {
 
     console.log("Minting AI character");
     await program.methods
-      .mintAinft(aiNftMetadata.name, aiNftMetadata.uri)
+      .mintAinft(aiNftMetadata.name, aiNftMetadata.uri, [])
       .accounts({
         payer: payer.publicKey,
         aiNft: appAinftPda,
         aiCharacter: aiCharacter,
         aiCharacterMint: aiCharacterMint,
         aiCharacterMetadata: aiCharacterMetadata,
+        masterMint: masterMint,
+        masterMetadata: masterMetadata,
+        masterEdition: masterEdition,
         payerAiCharacterTokenAccount: payerAiCharacterTokenAccount,
       })
       .signers([payer])
@@ -658,6 +681,7 @@ describe("ainft", async () => {
         computeMint: computeMint,
         signer: payer.publicKey,
         stakedMint: stakedMint,
+        protocolConfig,
       })
       .signers([payer])
       .rpc();
@@ -778,6 +802,7 @@ describe("ainft", async () => {
         aiCharacter: aiCharacter,
         computeTokenReceiver: aiCharacterComputeTokenAccount,
         senderComputeToken: senderComputeTokenAccount,
+        protocolConfig,
         sender: payer.publicKey,
       })
       .signers([payer])
@@ -801,6 +826,10 @@ describe("ainft", async () => {
         executionClient: executionClient,
         computeMint: computeMint,
         executionClientComputeTokenAddress: executionClientComputeAccount,
+        executionClientCharacterTokenAccount: null,
+        ownerRevenueTokenAccount: null,
+        protocolTreasuryTokenAccount: null,
+        protocolConfig,
         authority: payer.publicKey,
         tokenAAccount: null,
         tokenBAccount: null,
@@ -896,9 +925,10 @@ describe("ainft", async () => {
     await provider.connection.confirmTransaction(signature);
 
     // Create the AI NFT collection
-    const [appAinftPda] = findAppAinftPDA();
-    const [masterMint] = findMasterMintPDA();
+    const [appAinftPda] = findAppAinftPDA(payer.publicKey);
+    const [masterMint] = findMasterMintPDA(appAinftPda);
     const [masterMetadata] = findMetadataPDA(masterMint);
+    const [masterEdition] = findMasterEditionPDA(masterMint);
     const masterToken = await anchor.utils.token.associatedAddress({
       mint: masterMint,
       owner: payer.publicKey
@@ -906,12 +936,15 @@ describe("ainft", async () => {
 
     const defaultExecutionClient = anchor.web3.Keypair.generate().publicKey;
     const createAiNftParams = {
+      collectionId: new BN(0),
       name: "External Token Collection",
       uri: "https://example.com/external-token.json",
       symbol: "EXT",
       defaultExecutionClient: defaultExecutionClient,
       mintPrice: new BN(100),
+      paymentMint: null,
       maxSupply: new BN(100),
+      royalties: { sellerFeeBasisPoints: 500, creators: [], minterShare: 100 },
       computeMint: PublicKey.default,
     };
 
@@ -923,6 +956,7 @@ describe("ainft", async () => {
         masterMint: masterMint,
         masterToken: masterToken,
         masterMetadata: masterMetadata,
+        masterEdition: masterEdition,
         payer: payer.publicKey,
       })
       .signers([payer])
@@ -993,7 +1027,8 @@ describe("ainft", async () => {
       uri: "https://example.com/external-character.json",
     };
 
-    const [aiCharacterMint] = findAiCharacterMintPDA(appAinftPda, aiNftMetadata.name);
+    const { mintCount } = await program.account.aiNft.fetch(appAinftPda);
+    const [aiCharacterMint] = findAiCharacterMintPDA(appAinftPda, mintCount);
     const [aiCharacter] = findAiCharacterPDA(aiCharacterMint);
     const [aiCharacterMetadata] = findMetadataPDA(aiCharacterMint);
 
@@ -1004,13 +1039,16 @@ describe("ainft", async () => {
 
     console.log("Minting AI character");
     await program.methods
-      .mintAinft(aiNftMetadata.name, aiNftMetadata.uri)
+      .mintAinft(aiNftMetadata.name, aiNftMetadata.uri, [])
       .accounts({
         payer: payer.publicKey,
         aiNft: appAinftPda,
         aiCharacter: aiCharacter,
         aiCharacterMint: aiCharacterMint,
         aiCharacterMetadata: aiCharacterMetadata,
+        masterMint: masterMint,
+        masterMetadata: masterMetadata,
+        masterEdition: masterEdition,
         payerAiCharacterTokenAccount: payerAiCharacterTokenAccount,
       })
       .signers([payer])